@@ -1,11 +1,11 @@
 #![cfg_attr(debug_assertions, allow(unused_imports))]
 
 use proc_macro::{self, TokenStream};
-use proc_macro_error::abort;
-use proc_macro2::{Ident, Span, TokenStream as TokenStream2};
-use quote::quote;
+use proc_macro2::{Delimiter, Ident, Spacing, Span, TokenStream as TokenStream2, TokenTree};
+use quote::{ToTokens, quote};
 use syn::ext::IdentExt;
 use syn::parse::Parse;
+use syn::visit_mut::VisitMut;
 use syn::{Token, parse_macro_input};
 
 /**
@@ -13,8 +13,10 @@ A macro to compare types which implement `PartialOrd`.
 
 # Overview
 
-This macro performs comparison between two or three values of any type `T` which
-implements  `PartialOrd` and creates a `compare_variables::Comparison` struct.
+This macro performs comparison between a chain of two or more values of any type
+`T` which implements  `PartialOrd` and creates a `compare_variables::Comparison`
+struct. The first value may also have a different type than the following
+values, as long as it implements `PartialOrd` for their type.
 If the comparison evaluates to `true`, the struct is wrapped in [`Ok`],
 otherwise in [`Err`] to allow easy usage with the `?` operator.
 
@@ -27,9 +29,12 @@ for comparing two values and
 compare_variables(x _ y _ z)
 ```
 for comparing three values with `_` being any of the comparison operators
-`<, <=, ==, !=, >, >=`.
+`<, <=, ==, !=, >, >=`. The chain can be continued indefinitely, e.g.
+`compare_variables!(min <= low <= nominal <= high <= max)`. Each value is
+compared to its successor.
 
-`x`, `y` and `z` can be either a literal (e.g. `3.141` or `1e10`) or a variable:
+`x`, `y` and `z` can be any Rust expression, e.g. a literal (`3.141` or `1e10`)
+or a variable:
 
 ```rust
 use compare_variables::compare_variables;
@@ -75,6 +80,142 @@ assert!(compare_variables!(a.0 > -6).is_ok());
 assert!(compare_variables!(a.0 > 1).is_err());
 ```
 
+Method calls, indexing, arithmetic and other expressions are supported as well.
+The source code of the expression is then used as its name in the message:
+
+```
+use compare_variables::compare_variables;
+
+let v = [1, 2, 3];
+let i = 1;
+assert!(compare_variables!(v.len() > 0).is_ok());
+assert!(compare_variables!(v[i] + 1 <= v[2]).is_ok());
+
+let err = compare_variables!(v.iter().sum::<i32>() < 6).unwrap_err().to_string();
+assert_eq!(err, "`v.iter().sum::<i32>() (value: 6) < 6` is false");
+```
+
+Since `as` is used to give an operand an alias (see below), casts need to be
+put in parentheses:
+
+```
+use compare_variables::compare_variables;
+
+let x = 3u8;
+let err = compare_variables!((x as f64) < 2.5).unwrap_err().to_string();
+assert_eq!(err, "`(x as f64) (value: 3.0) < 2.5` is false");
+```
+
+# Borrowing and ownership
+
+The operands are borrowed, i.e. the macro creates a `Comparison<&T>` and
+variables are not moved. A borrowed comparison can be converted into an owned
+one via `Comparison::into_owned` or `From` (used implicitly by the `?`
+operator). Alternatively, the keyword `owned` in front of the first operand
+moves all operands into the comparison. This is required to store a
+comparison of temporary values (e.g. of method call results) beyond the end of
+the statement:
+
+```
+use compare_variables::{compare_variables, Comparison};
+
+let name = String::from("Bob");
+let other = String::from("Alice");
+let borrowed: Comparison<&String> = compare_variables!(name < other).unwrap_err();
+let owned: Comparison<String> = borrowed.into_owned();
+assert_eq!(owned.to_string(), "`name (value: \"Bob\") < other (value: \"Alice\")` is false");
+
+let err: Comparison<usize> = compare_variables!(owned name.len() > 3).unwrap_err();
+assert_eq!(err.to_string(), "`name.len() (value: 3) > 3` is false");
+```
+
+`owned` is recognized as keyword if it is followed by an identifier, a literal
+or an expression in parentheses or braces. Otherwise, it is treated as a
+variable name.
+
+# Approximate equality
+
+Floating point values can be compared with a tolerance using the operator `~=`
+followed by the tolerance: `abs` (absolute deviation), `rel` (relative
+deviation) or `ulps` (units in the last place). The deviation and the tolerance
+are then included in the message:
+
+```
+use compare_variables::compare_variables;
+
+let x = 0.1 + 0.2;
+assert!(compare_variables!(x == 0.3).is_err());
+assert!(compare_variables!(x ~= 0.3, abs = 1e-9).is_ok());
+assert!(compare_variables!(x ~= 0.3, rel = 1e-6).is_ok());
+assert!(compare_variables!(x ~= 0.3, ulps = 4).is_ok());
+
+let err = compare_variables!(x ~= 0.4, abs = 1e-9).unwrap_err();
+assert_eq!(
+    err.to_string(),
+    "`x (value: 0.30000000000000004) ~= 0.4` is false \
+    (absolute deviation: 0.09999999999999998, tolerance: 1e-9)"
+);
+```
+
+The operator `~=` is available for all types implementing
+`compare_variables::ApproxEq` and cannot be chained.
+
+# Range membership
+
+The keyword `in` checks whether a value lies within a range. The range can
+either be written as a range expression (`lo..hi`, `lo..=hi`, `lo..`, `..hi` or
+`..=hi`), whose bounds are then displayed like other operands, or be any value
+implementing `std::ops::RangeBounds` (e.g. a constant):
+
+```
+use compare_variables::compare_variables;
+
+const VALID: std::ops::RangeInclusive<i32> = 0..=1;
+
+let x = 2;
+assert!(compare_variables!(x in 0..=2).is_ok());
+assert!(compare_variables!(x in ..10).is_ok());
+assert!(compare_variables!(x in 5..).is_err());
+
+let err = compare_variables!(x in 0..=1).unwrap_err();
+assert_eq!(err.to_string(), "`x (value: 2) in 0..=1` is false");
+
+let lo = 3;
+let err = compare_variables!(x in lo..).unwrap_err();
+assert_eq!(err.to_string(), "`x (value: 2) in lo (value: 3)..` is false");
+
+let err = compare_variables!(x in VALID).unwrap_err();
+assert_eq!(err.to_string(), "`x (value: 2) in VALID (value: 0..=1)` is false");
+```
+
+# Logical combinations
+
+Comparisons can be combined with the logical operators `&&` and `||` and
+negated via `!(...)`. Operands which are not comparisons (e.g. `allow_zero`)
+must evaluate to a `bool`. Like in Rust, `&&` binds more tightly than `||`,
+parentheses can be used for grouping and the right-hand side of `&&` and `||`
+is only evaluated if needed. In this case, the macro returns a
+`compare_variables::Condition` instead of a `compare_variables::Comparison`.
+Custom messages are only supported by `assert_compare!` and
+`debug_assert_compare!` for logical combinations.
+
+Deferred comparisons (i.e. all but the first operand of `&&` and `||`) which
+borrow temporary values (e.g. `v.len() > 0`) are converted into owned
+comparisons via `Comparison::into_owned`, which requires the values to
+implement `Clone`.
+
+```
+use compare_variables::compare_variables;
+
+let (x, allow_zero) = (0, false);
+assert!(compare_variables!(x > 0 || x == 0).is_ok());
+let ratio = 0.5;
+assert!(compare_variables!(!(x < 0) && (ratio ~= 0.5, abs = 1e-9)).is_ok());
+
+let err = compare_variables!(x > 0 || allow_zero).unwrap_err();
+assert_eq!(err.to_string(), "`x (value: 0) > 0` is false || `allow_zero` is false");
+```
+
 # Customizing the message
 
 The keywords `val` and `as` allow to customize the treatment of variable names
@@ -107,6 +248,41 @@ let err = compare_variables!(val x as variable > y).unwrap_or_else(|x| x);
 assert_eq!(err.to_string(), "`1 > y (value: 2)` is false");
 ```
 
+# Custom messages
+
+A custom message in the [`format!`] syntax can be given after the comparison.
+It is only formatted if the comparison does not evaluate to true and appended
+to the generated message:
+
+```
+use compare_variables::compare_variables;
+
+const MAX: u32 = 3;
+let retries = 4;
+let host = "example.com";
+let err = compare_variables!(retries <= MAX, "retry budget exhausted for {}", host).unwrap_err();
+assert_eq!(err.message(), Some("retry budget exhausted for example.com"));
+assert_eq!(
+    err.to_string(),
+    "`retries (value: 4) <= MAX (value: 3)` is false: retry budget exhausted for example.com"
+);
+```
+
+# Source location
+
+The location of the macro invocation is stored in the resulting comparison and
+appended to the message when using the alternate format `{:#}`:
+
+```
+use compare_variables::compare_variables;
+
+let x = 1;
+let err = compare_variables!(x > 2).unwrap_err();
+let location = err.location().unwrap();
+assert_eq!(location.line, line!() - 2);
+assert_eq!(format!("{err:#}"), format!("`x (value: 1) > 2` is false at {location}"));
+```
+
 # Examples
 
 ```rust
@@ -147,313 +323,2792 @@ assert!(compare_variables!(myfloat1 >= myfloat2).is_err());
  */
 #[proc_macro]
 pub fn compare_variables(input: TokenStream) -> TokenStream {
-    let comparison_error_info: ComparisonErrorInfo = parse_macro_input!(input);
-
-    let first_arg = comparison_error_info.first_arg.as_token_stream();
-    let relation_first_to_second = comparison_error_info
-        .relation_first_to_second
-        .as_token_stream();
-    let second_arg = comparison_error_info.second_arg.as_token_stream();
-    let relation_second_to_third = comparison_error_info
-        .relation_second_to_third
-        .as_token_stream();
-    let third_arg = match comparison_error_info.third_arg {
-        Some(arg) => {
-            let ts = arg.as_token_stream();
-            quote! {Some(#ts)}
-        }
-        None => quote! {None},
-    };
-
-    // Build the input for the compare_variables function
-    let stream = quote! {
-        compare_variables::Comparison::new_checked(
-            #first_arg,
-            #relation_first_to_second,
-            #second_arg,
-            #relation_second_to_third,
-            #third_arg,
-        )
-    };
+    let message_info: MessageInfo = parse_macro_input!(input);
 
-    return TokenStream::from(stream);
+    return TokenStream::from(message_info.as_token_stream());
 }
 
-#[repr(u8)]
-enum Operator {
-    Lesser,
-    LesserOrEqual,
-    Equal,
-    Inequal,
-    GreaterOrEqual,
-    Greater,
-}
+/**
+Asserts that a comparison is true and panics otherwise.
 
-impl Operator {
-    fn as_token_stream(&self) -> proc_macro2::TokenStream {
-        match self {
-            Operator::Lesser => {
-                quote! {
-                    compare_variables::ComparisonOperator::Lesser
-                }
-            }
-            Operator::LesserOrEqual => {
-                quote! {
-                    compare_variables::ComparisonOperator::LesserOrEqual
-                }
-            }
-            Operator::Equal => {
-                quote! {
-                    compare_variables::ComparisonOperator::Equal
-                }
-            }
-            Operator::Inequal => {
-                quote! {
-                    compare_variables::ComparisonOperator::Inequal
-                }
-            }
-            Operator::GreaterOrEqual => {
-                quote! {
-                    compare_variables::ComparisonOperator::GreaterOrEqual
-                }
-            }
-            Operator::Greater => {
-                quote! {
-                    compare_variables::ComparisonOperator::Greater
-                }
-            }
-        }
-    }
-}
+This macro is a drop-in replacement for [`assert!`] which accepts the same
+syntax as `compare_variables!`. If the comparison does not evaluate to true,
+it panics with the message of the resulting `compare_variables::Comparison`.
+Like for [`assert!`], an optional custom message in the [`format!`] syntax can
+be given after the comparison, which is then appended to the panic message.
 
-impl Parse for Operator {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        // If Token![<] is tested before Token![<=], then "<" is parsed, leaving only
-        // "=". This will then lead to a compile error.
-        if input.peek(Token![<=]) {
-            input.parse::<Token![<=]>()?;
-            Ok(Operator::LesserOrEqual)
-        } else if input.peek(Token![>=]) {
-            input.parse::<Token![>=]>()?;
-            Ok(Operator::GreaterOrEqual)
-        } else if input.peek(Token![==]) {
-            input.parse::<Token![==]>()?;
-            Ok(Operator::Equal)
-        } else if input.peek(Token![!=]) {
-            input.parse::<Token![!=]>()?;
-            Ok(Operator::Inequal)
-        } else if input.peek(Token![<]) {
-            input.parse::<Token![<]>()?;
-            Ok(Operator::Lesser)
-        } else if input.peek(Token![>]) {
-            input.parse::<Token![>]>()?;
-            Ok(Operator::Greater)
-        } else {
-            Err(syn::Error::new(
-                input.span(),
-                "no comparison operator could be identified. Valid
-                    operators are \"<\", \"<=\", \"==\", \"!=\", \">=\" or \">\".",
-            ))
-        }
-    }
-}
+# Examples
 
-enum VariableOrLiteral {
-    Other {
-        arg_names: Vec<String>,
-        arg_names_display: Vec<String>,
-    },
-    LitFloat(syn::LitFloat),
-    LitInt(syn::LitInt),
-}
+```
+use compare_variables::assert_compare;
 
-impl VariableOrLiteral {
-    fn as_token_stream(&self) -> proc_macro2::TokenStream {
-        match self {
-            VariableOrLiteral::Other {
-                arg_names,
-                arg_names_display,
-            } => {
-                // Build a token stream out of arg_name and arg_name_display, using . as a
-                // delimiter
-                let arg_value = arg_names.join(".");
-                let arg_value_ts: TokenStream2 = match str::parse::<TokenStream2>(&arg_value) {
-                    Ok(ts) => ts,
-                    Err(_) => abort!(
-                        Span::call_site(),
-                        format!("could not interpret {arg_value} as rust code")
-                    ),
-                };
-                if arg_names_display.is_empty() {
-                    quote! {
-                        compare_variables::ComparisonValue::new(#arg_value_ts, None)
-                    }
-                } else {
-                    let arg_name_display = arg_names_display.join(".");
-                    quote! {
-                        compare_variables::ComparisonValue::new(#arg_value_ts, Some(#arg_name_display))
-                    }
-                }
-            }
-            VariableOrLiteral::LitFloat(lit) => {
-                quote! {
-                    compare_variables::ComparisonValue::new(#lit, None)
-                }
-            }
-            VariableOrLiteral::LitInt(lit) => {
-                quote! {
-                    compare_variables::ComparisonValue::new(#lit, None)
-                }
-            }
-        }
-    }
+let x = 1;
+assert_compare!(0 < x <= 1);
+assert_compare!(x in 0..10, "x is out of range");
+```
+
+```should_panic
+use compare_variables::assert_compare;
+
+let x = 2;
+let path = "config.toml";
+// Panics with: `x (value: 2) <= 1` is false: invalid value in config.toml
+assert_compare!(x <= 1, "invalid value in {}", path);
+```
+ */
+#[proc_macro]
+pub fn assert_compare(input: TokenStream) -> TokenStream {
+    let message_info: MessageInfo = parse_macro_input!(input);
+    return TokenStream::from(message_info.as_assert_token_stream());
 }
 
-impl Parse for VariableOrLiteral {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        fn parse_composite_varname(
-            input: &syn::parse::ParseStream,
-            vec: &mut Vec<String>,
-        ) -> syn::Result<()> {
-            loop {
-                if input.peek(syn::LitInt) {
-                    let lit = input.parse::<syn::LitInt>()?;
-                    vec.push(lit.to_string());
-                } else {
-                    let ident: syn::Ident = input.call(Ident::parse_any)?;
-                    vec.push(ident.to_string()); // parse_any also handles stuff like self
-                }
+/**
+Asserts that a comparison is true if debug assertions are enabled.
 
-                if input.peek(Token![.]) {
-                    // Throw the token away
-                    let _ = input.parse::<Token![.]>()?;
-                } else {
-                    // Field access is done ==> Finish the loop
-                    break;
-                }
-            }
-            return Ok(());
-        } // parse_composite_varname
+This macro behaves like `assert_compare!` in builds with debug assertions
+and is a drop-in replacement for [`debug_assert!`]. Like [`debug_assert!`], the
+comparison is not evaluated if debug assertions are disabled (e.g. in
+optimized builds).
 
-        if input.peek(syn::LitFloat) {
-            // Parse the float literal
-            let val = input.parse::<syn::LitFloat>()?;
-            return Ok(VariableOrLiteral::LitFloat(val));
-        } else if input.peek(syn::LitInt) {
-            // Parse the float literal
-            let val = input.parse::<syn::LitInt>()?;
-            return Ok(VariableOrLiteral::LitInt(val));
-        } else {
-            let mut display_arg_names = true;
+# Examples
 
-            // Input is possibly a variable name.
-            let mut arg_names: Vec<String> = Vec::new();
+```
+use compare_variables::debug_assert_compare;
 
-            // First check if the first identifier is "val":
-            let first_ident: Ident = input.call(Ident::parse_any)?; // parse_any also handles stuff like self
+let x = 1.0;
+debug_assert_compare!(x ~= 1.0, abs = 1e-9);
+debug_assert_compare!(x > 0.0, "x must be positive, got {x}");
+```
+ */
+#[proc_macro]
+pub fn debug_assert_compare(input: TokenStream) -> TokenStream {
+    let message_info: MessageInfo = parse_macro_input!(input);
+    let assertion = message_info.as_assert_token_stream();
+    return TokenStream::from(quote! {
+        if cfg!(debug_assertions) {
+            #assertion
+        }
+    });
+}
 
-            // Next identifier is not a "." -> Check if first_ident is "val". If not, the
-            // macro is used wrong
-            if input.peek(Token![.]) {
-                // Throw the token away
-                let _ = input.parse::<Token![.]>()?;
+/**
+Returns early with an error if a comparison is not true.
 
-                // Try continuing to parse and keep the first identifier
-                arg_names.push(first_ident.to_string());
-                parse_composite_varname(&input, &mut arg_names)?;
-            } else {
-                if input.peek(syn::Ident) {
-                    if first_ident == "val" {
-                        display_arg_names = false;
+This macro accepts the same syntax as `compare_variables!`. If the comparison
+does not evaluate to true, the enclosing function returns an error which is
+converted via [`From`] into the error type of the function (like the `?`
+operator does). The error can be specified in three ways:
 
-                        // Try continuing to parse
-                        parse_composite_varname(&input, &mut arg_names)?;
-                    } else {
-                        abort!(
-                            Span::call_site(),
-                            format!("found unexpected tokens behind {first_ident}")
-                        )
-                    }
-                } else {
-                    arg_names.push(first_ident.to_string());
-                }
-            }
+- `ensure_compare!(x > 0)`: The `compare_variables::Comparison` itself is
+returned as the error.
+- `ensure_compare!(x > 0, error)`: The expression `error` is returned. It is
+only evaluated if the comparison is not true.
+- `ensure_compare!(x > 0 => function)`: The function (or closure) is called
+with the `compare_variables::Comparison` and its output is returned.
 
-            // Resolve the alias, if the variable name should be displayed
-            let arg_names_display: Vec<String> = if input.peek(Token![as]) {
-                input.parse::<Token![as]>()?;
-                let mut arg_names_display: Vec<String> = Vec::new();
-                parse_composite_varname(&input, &mut arg_names_display)?;
-                if display_arg_names {
-                    arg_names_display
-                } else {
-                    Vec::new()
-                }
-            } else {
-                if display_arg_names {
-                    arg_names.clone()
-                } else {
-                    Vec::new()
-                }
-            };
+Unless the `owned` mode is used, the comparison is converted into an owned
+comparison via `Comparison::into_owned` before it is passed on.
 
-            return Ok(VariableOrLiteral::Other {
-                arg_names,
-                arg_names_display,
-            });
-        }
-    }
+# Examples
+
+```
+use compare_variables::{ensure_compare, Comparison};
+
+#[derive(Debug)]
+enum MyError {
+    Negative,
+    OutOfRange(Comparison<f64>),
+    Message(String),
 }
 
-// Parser for the compare_variables macro
-struct ComparisonErrorInfo {
-    first_arg: VariableOrLiteral,
-    relation_first_to_second: Operator,
-    second_arg: VariableOrLiteral,
-    relation_second_to_third: Operator,
-    third_arg: Option<VariableOrLiteral>,
+fn check(x: f64) -> Result<f64, MyError> {
+    ensure_compare!(x >= 0.0, MyError::Negative);
+    ensure_compare!(x <= 100.0 => MyError::OutOfRange);
+    ensure_compare!(x != 50.0 => |c| MyError::Message(c.to_string()));
+    return Ok(x);
 }
 
-impl Parse for ComparisonErrorInfo {
-    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
-        // Read the arguments
-        let first_arg = VariableOrLiteral::parse(&input)?;
-        let relation_first_to_second = Operator::parse(&input)?;
-        let second_arg = VariableOrLiteral::parse(&input)?;
-
-        // If the input continues, parse the third argument
-        let (relation_second_to_third, third_arg) = if let Ok(operator) = Operator::parse(&input) {
-            (operator, Some(VariableOrLiteral::parse(&input)?))
-        } else {
-            (Operator::Equal, None)
-        };
+assert!(check(1.0).is_ok());
+assert!(matches!(check(-1.0), Err(MyError::Negative)));
+assert!(matches!(check(120.0), Err(MyError::OutOfRange(_))));
+let Err(MyError::Message(message)) = check(50.0) else { panic!() };
+assert_eq!(message, "`x (value: 50.0) != 50.0` is false");
 
-        return Ok(ComparisonErrorInfo {
-            first_arg,
-            relation_first_to_second,
-            second_arg,
-            relation_second_to_third,
-            third_arg,
-        });
-    }
+fn check_comparison(x: i32) -> Result<i32, Box<dyn std::error::Error>> {
+    ensure_compare!(x in 0..10);
+    return Ok(x);
+}
+assert_eq!(check_comparison(10).unwrap_err().to_string(), "`x (value: 10) in 0..10` is false");
+```
+ */
+#[proc_macro]
+pub fn ensure_compare(input: TokenStream) -> TokenStream {
+    let ensure_info: EnsureInfo = parse_macro_input!(input);
+    return TokenStream::from(ensure_info.as_token_stream());
 }
 
-#[cfg(test)]
-mod tests {
+/**
+Evaluates a list of comparisons and collects all failures.
 
-    use super::*;
+This macro accepts a comma-separated list of comparisons in the syntax of
+`compare_variables!`. All comparisons are evaluated and recorded in a
+`compare_variables::ComparisonSet`. The macro returns `Ok(())` if all
+comparisons are true and the set of comparisons otherwise, which lists every
+failed comparison. Unless the `owned` mode is used, failed comparisons are
+converted into owned comparisons via `Comparison::into_owned`.
 
-    #[test]
-    fn test_parse_check_bounds_info() {
-        // Assert that the parse was successfull
+# Examples
 
-        // Float
-        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 < arg);
-        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 <= arg);
-        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 <= arg as alternative_arg);
-        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 < arg <= 1.0);
-        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 < arg as alternative_arg <= 1.0);
-        let _: ComparisonErrorInfo = syn::parse_quote!(arg < 1.0);
+```
+use compare_variables::{validate, ComparisonSet};
+
+fn check(a: i32, b: f64, c: char, d: char) -> Result<(), ComparisonSet> {
+    return validate! {
+        a > 0,
+        b <= 10.0,
+        c != d,
+    };
+}
+
+assert!(check(1, 5.0, 'x', 'y').is_ok());
+
+let err = check(0, 20.0, 'x', 'y').unwrap_err();
+assert_eq!(err.passed(), 1);
+assert_eq!(err.failed(), 2);
+assert_eq!(
+    err.to_string(),
+    "2 of 3 comparisons failed:\n\
+     - `a (value: 0) > 0` is false\n\
+     - `b (value: 20.0) <= 10.0` is false"
+);
+```
+ */
+#[proc_macro]
+pub fn validate(input: TokenStream) -> TokenStream {
+    let validate_info: ValidateInfo = parse_macro_input!(input);
+    return TokenStream::from(validate_info.as_token_stream());
+}
+
+/**
+Derives `compare_variables::Validate` from comparisons attached to the fields.
+
+The following field attributes are supported:
+
+- `#[check(...)]`: A comma-separated list of comparisons in the syntax of
+`validate!`. The placeholder `_` stands for the annotated field, other fields
+can be referenced via `self.field`. Fields are named by their path, e.g.
+`limits.max` for the field `max` of the nested field `limits`.
+- `#[validate]`: The field implements `compare_variables::Validate` itself
+and is validated as well.
+
+The derived `validate` method records all comparisons and returns the failed
+ones in a `compare_variables::ComparisonSet`.
+
+# Examples
+
+```
+use compare_variables::Validate;
+
+#[derive(Validate)]
+struct Cfg {
+    min: u32,
+    #[check(0.0 < _ <= 1.0)]
+    ratio: f64,
+    #[check(_ >= self.min, _ != 0)]
+    max: u32,
+}
+
+let cfg = Cfg { min: 2, ratio: 0.5, max: 1 };
+let err = cfg.validate().unwrap_err();
+assert_eq!(
+    err.to_string(),
+    "1 of 3 comparisons failed:\n\
+     - `max (value: 1) >= min (value: 2)` is false"
+);
+```
+ */
+#[proc_macro_derive(Validate, attributes(check, validate))]
+pub fn derive_validate(input: TokenStream) -> TokenStream {
+    let input: syn::DeriveInput = parse_macro_input!(input);
+    return match validate_impl(&input) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    };
+}
+
+/// Returns the implementation of `compare_variables::Validate` for `input`.
+fn validate_impl(input: &syn::DeriveInput) -> syn::Result<TokenStream2> {
+    let syn::Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "Validate can only be derived for structs",
+        ));
+    };
+
+    let path = mixed_site_ident("path");
+    let set = mixed_site_ident("set");
+
+    let mut checks = Vec::new();
+    for (index, field) in data.fields.iter().enumerate() {
+        let (member, name) = match &field.ident {
+            Some(ident) => (quote! {#ident}, ident.unraw().to_string()),
+            None => {
+                let index = syn::Index::from(index);
+                (quote! {#index}, index.index.to_string())
+            }
+        };
+        for attr in field.attrs.iter() {
+            if attr.path().is_ident("check") {
+                let mut info: ValidateInfo = attr.parse_args()?;
+                for comparison in info.comparisons.iter_mut() {
+                    for operand in comparison.operands_mut() {
+                        operand.resolve_fields(&quote! {self.#member}, &path);
+                    }
+                    checks.push(ValidateInfo::check_token_stream(comparison, &set));
+                }
+            } else if attr.path().is_ident("validate") {
+                attr.meta.require_path_only()?;
+                checks.push(quote! {
+                    compare_variables::Validate::validate_into(
+                        &self.#member,
+                        &compare_variables::field_path(#path, #name),
+                        #set,
+                    );
+                });
+            }
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    return Ok(quote! {
+        impl #impl_generics compare_variables::Validate for #ident #ty_generics #where_clause {
+            #[allow(unused_variables)]
+            fn validate_into(&self, #path: &str, #set: &mut compare_variables::ComparisonSet) {
+                #(#checks)*
+            }
+        }
+    });
+}
+
+/**
+Checks a precondition of a function.
+
+The attribute accepts a comparison in the syntax of `compare_variables!` (which
+usually refers to the arguments of the function), optionally followed by the
+mode `, mode = panic`, `, mode = result` or `, mode = debug`:
+
+- `panic` (default): The function panics if the comparison is not true.
+- `result`: The function returns the comparison as its error (converted via
+[`From`] like in `ensure_compare!`). The function must therefore return a
+[`Result`].
+- `debug`: Like `panic`, but only checked if debug assertions are enabled.
+
+The contracts of a function (`#[requires]` and `#[ensures]`) are checked in the
+order they are written in and appended to the documentation of the function in
+a section "Contract".
+
+# Examples
+
+```
+use compare_variables::{requires, Comparison};
+
+#[requires(0.0 <= p <= 1.0)]
+fn bernoulli_variance(p: f64) -> f64 {
+    return p * (1.0 - p);
+}
+
+#[requires(len > 0, mode = result)]
+#[requires(len <= 64, mode = result)]
+fn buffer(len: usize) -> Result<Vec<u8>, Comparison<usize>> {
+    return Ok(vec![0; len]);
+}
+
+assert_eq!(bernoulli_variance(0.5), 0.25);
+assert_eq!(
+    buffer(0).unwrap_err().to_string(),
+    "`len (value: 0) > 0` is false"
+);
+```
+
+```should_panic
+use compare_variables::requires;
+
+#[requires(0.0 <= p <= 1.0)]
+fn bernoulli_variance(p: f64) -> f64 {
+    return p * (1.0 - p);
+}
+
+// Panics with: precondition of `bernoulli_variance` violated: `0.0 <= p (value: 2.0) <= 1.0` is false (...)
+bernoulli_variance(2.0);
+```
+ */
+#[proc_macro_attribute]
+pub fn requires(attr: TokenStream, item: TokenStream) -> TokenStream {
+    return contract(ContractKind::Requires, attr, item);
+}
+
+/**
+Checks a postcondition of a function.
+
+The attribute accepts a comparison in the syntax of `compare_variables!`, in
+which `ret` refers to the return value of the function, and the same modes as
+`#[requires]`. In the mode `result`, the function must return a [`Result`]:
+`ret` then refers to the value wrapped in [`Ok`] and the postcondition is only
+checked if the function succeeded.
+
+The postconditions are also checked if the body returns early via `return` or
+`?` (`return` and `?` within macro invocations are not rewritten and skip the
+postconditions). The `?` operator is supported for [`Result`] and [`Option`].
+Arguments which are moved by the body cannot be used in the postcondition.
+
+# Examples
+
+```
+use compare_variables::{ensures, requires, Comparison};
+
+#[ensures(ret >= input)]
+fn round_up_to_even(input: u32) -> u32 {
+    if input.is_multiple_of(2) {
+        return input;
+    }
+    return input + 1;
+}
+
+#[requires(divisor != 0, mode = result)]
+#[ensures(ret <= dividend, mode = result)]
+fn divide(dividend: u32, divisor: u32) -> Result<u32, Comparison<u32>> {
+    return Ok(dividend / divisor);
+}
+
+assert_eq!(round_up_to_even(3), 4);
+assert_eq!(divide(6, 3).unwrap(), 2);
+assert!(divide(6, 0).is_err());
+```
+ */
+#[proc_macro_attribute]
+pub fn ensures(attr: TokenStream, item: TokenStream) -> TokenStream {
+    return contract(ContractKind::Ensures, attr, item);
+}
+
+/// Expands the contract attributes of a function, see `requires` and `ensures`.
+fn contract(kind: ContractKind, attr: TokenStream, item: TokenStream) -> TokenStream {
+    let function: syn::ItemFn = parse_macro_input!(item);
+    return match contract_impl(kind, attr.into(), function) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    };
+}
+
+fn contract_impl(
+    kind: ContractKind,
+    attr: TokenStream2,
+    mut function: syn::ItemFn,
+) -> syn::Result<TokenStream2> {
+    // All contracts of the function are expanded at once, so they are checked
+    // in the order they are written in.
+    let mut contracts = vec![ContractInfo::parse(kind, attr)?];
+    let mut attrs = Vec::with_capacity(function.attrs.len());
+    for attr in std::mem::take(&mut function.attrs) {
+        match ContractKind::from_path(attr.path()) {
+            Some(kind) => contracts.push(ContractInfo::parse(
+                kind,
+                attr.meta.require_list()?.tokens.clone(),
+            )?),
+            None => attrs.push(attr),
+        }
+    }
+    function.attrs = attrs;
+
+    // Document the contracts
+    let mut docs = vec![String::new(), String::from(" # Contract"), String::new()];
+    docs.extend(contracts.iter().map(ContractInfo::doc));
+    function
+        .attrs
+        .extend(docs.iter().map(|doc| syn::parse_quote!(#[doc = #doc])));
+
+    let name = function.sig.ident.to_string();
+    let requires = contracts
+        .iter()
+        .filter(|contract| contract.kind == ContractKind::Requires)
+        .map(|contract| contract.check_token_stream(&name));
+    let (result_ensures, ensures): (Vec<&ContractInfo>, Vec<&ContractInfo>) = contracts
+        .iter()
+        .filter(|contract| contract.kind == ContractKind::Ensures)
+        .partition(|contract| contract.mode == ContractMode::Result);
+
+    let block = &function.block;
+    let statements = &block.stmts;
+    let body = if ensures.is_empty() && result_ensures.is_empty() {
+        quote! {{
+            #(#requires)*
+            #(#statements)*
+        }}
+    } else {
+        // The return value is named `ret` in the postconditions
+        let ret = Ident::new("ret", Span::call_site());
+        let body = bind_return_value(&function.sig, block, &ret);
+        let ensures = ensures
+            .iter()
+            .map(|contract| contract.check_token_stream(&name));
+        let output = if result_ensures.is_empty() {
+            quote! {#ret}
+        } else {
+            let result_ensures = result_ensures
+                .iter()
+                .map(|contract| contract.check_token_stream(&name));
+            let error = mixed_site_ident("error");
+            quote! {
+                match #ret {
+                    Ok(#ret) => {
+                        #(#result_ensures)*
+                        Ok(#ret)
+                    }
+                    Err(#error) => Err(#error),
+                }
+            }
+        };
+        quote! {{
+            #(#requires)*
+            #body
+            #(#ensures)*
+            #output
+        }}
+    };
+    function.block = Box::new(syn::parse2(body)?);
+    return Ok(function.into_token_stream());
+}
+
+/**
+Returns the statement which evaluates the body of a function and binds its
+return value to `ret`. The body is evaluated as a labeled block, in which
+`return` and `?` are rewritten to break out of the block (see `EarlyReturns`),
+so the statements after the body are also executed on early returns.
+ */
+fn bind_return_value(signature: &syn::Signature, block: &syn::Block, ret: &Ident) -> TokenStream2 {
+    let mut block = block.clone();
+    // A final `return value;` becomes the value of the block
+    if let Some(syn::Stmt::Expr(syn::Expr::Return(return_expr), Some(_))) = block.stmts.last() {
+        let value = match &return_expr.expr {
+            Some(value) => (**value).clone(),
+            None => syn::parse_quote!(()),
+        };
+        *block.stmts.last_mut().unwrap() = syn::Stmt::Expr(value, None);
+    }
+    let mut early_returns = EarlyReturns {
+        label: syn::Lifetime::new("'body", Span::mixed_site()),
+        found: false,
+    };
+    early_returns.visit_block_mut(&mut block);
+    let label = &early_returns.label;
+    let label = if early_returns.found {
+        quote! {#label:}
+    } else {
+        quote! {}
+    };
+
+    // `impl Trait` is not allowed in the type of a variable
+    let ty = match &signature.output {
+        syn::ReturnType::Type(_, ty)
+            if !contains_token(ty.to_token_stream(), &|token| {
+                return matches!(token, TokenTree::Ident(ident) if ident == "impl");
+            }) =>
+        {
+            quote! {: #ty}
+        }
+        syn::ReturnType::Type(..) => quote! {},
+        syn::ReturnType::Default => quote! {: ()},
+    };
+    return quote! {
+        let #ret #ty = #label #block;
+    };
+}
+
+/**
+Rewrites `return` and `?` within a function body into breaks out of the labeled
+block `label`. Closures, async blocks and nested items are skipped, as `return`
+and `?` refer to them instead of the function.
+ */
+struct EarlyReturns {
+    label: syn::Lifetime,
+    /// Whether any `return` or `?` has been rewritten
+    found: bool,
+}
+
+impl VisitMut for EarlyReturns {
+    fn visit_expr_mut(&mut self, expr: &mut syn::Expr) {
+        match expr {
+            syn::Expr::Closure(_) | syn::Expr::Async(_) => (),
+            syn::Expr::Return(return_expr) => {
+                if let Some(value) = return_expr.expr.as_mut() {
+                    self.visit_expr_mut(value);
+                }
+                let label = &self.label;
+                let value = &return_expr.expr;
+                *expr = syn::parse_quote!(break #label #value);
+                self.found = true;
+            }
+            syn::Expr::Try(try_expr) => {
+                self.visit_expr_mut(&mut try_expr.expr);
+                let label = &self.label;
+                let value = &try_expr.expr;
+                let output = mixed_site_ident("output");
+                let residual = mixed_site_ident("residual");
+                let branch: syn::Expr = syn::parse_quote! {
+                    match compare_variables::contract::Try::branch(#value) {
+                        compare_variables::contract::Branch::Continue(#output) => #output,
+                        compare_variables::contract::Branch::Break(#residual) => break #label #residual,
+                    }
+                };
+                // The invisible group keeps e.g. `value?.len();` a single statement
+                *expr = syn::Expr::Group(syn::ExprGroup {
+                    attrs: Vec::new(),
+                    group_token: Default::default(),
+                    expr: Box::new(branch),
+                });
+                self.found = true;
+            }
+            _ => syn::visit_mut::visit_expr_mut(self, expr),
+        }
+    }
+
+    fn visit_item_mut(&mut self, _item: &mut syn::Item) {}
+}
+
+/// Returns true if any token of `tokens` (including the tokens within groups)
+/// satisfies `predicate`.
+fn contains_token(tokens: TokenStream2, predicate: &dyn Fn(&TokenTree) -> bool) -> bool {
+    return tokens.into_iter().any(|token| match &token {
+        TokenTree::Group(group) => return contains_token(group.stream(), predicate),
+        token => return predicate(token),
+    });
+}
+
+/**
+Checks an invariant of a type after every method of an `impl` block which
+modifies or creates a value of the type.
+
+The attribute accepts a comparison in the syntax of `compare_variables!`, in
+which the value is referred to as `self`, optionally followed by the mode
+`, mode = panic` (default) or `, mode = debug` (only checked if debug assertions
+are enabled). Multiple invariants can be given via multiple attributes.
+
+The invariants are checked
+- after every method taking `&mut self`, for the modified value, and
+- after every other function returning `Self` (e.g. constructors or methods
+taking `self`), for the returned value. If the function returns
+`Result<Self, E>` or `Option<Self>`, the invariants are only checked for
+`Ok` and `Some` values.
+
+Methods taking `&mut self` whose return type contains a reference, a lifetime
+or `impl Trait` (e.g. `fn lo_mut(&mut self) -> &mut i32`) are not checked,
+since the returned value may borrow `self` while the invariants would be
+evaluated. Changes made through such a borrow are therefore not checked either.
+Lifetimes which are elided within paths have to be written as `'_` (e.g.
+`IterMut<'_, T>` instead of `IterMut<T>`) for the method to be recognized.
+
+A violated invariant panics with the comparison, which carries the name of the
+type and of the method as its message. The operands are named after the source
+code, i.e. `self.field`.
+
+# Examples
+
+```should_panic
+use compare_variables::invariant;
+
+struct Interval {
+    lo: i32,
+    hi: i32,
+}
+
+#[invariant(self.lo <= self.hi)]
+impl Interval {
+    fn new(lo: i32, hi: i32) -> Self {
+        return Interval { lo, hi };
+    }
+
+    fn set_lo(&mut self, lo: i32) {
+        self.lo = lo;
+    }
+}
+
+let mut interval = Interval::new(1, 3);
+// Panics with: `self.lo (value: 5) <= self.hi (value: 3)` is false: invariant of `Interval` violated by `set_lo`
+interval.set_lo(5);
+```
+ */
+#[proc_macro_attribute]
+pub fn invariant(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let implementation: syn::ItemImpl = parse_macro_input!(item);
+    return match invariant_impl(attr.into(), implementation) {
+        Ok(tokens) => TokenStream::from(tokens),
+        Err(error) => TokenStream::from(error.to_compile_error()),
+    };
+}
+
+fn invariant_impl(
+    attr: TokenStream2,
+    mut implementation: syn::ItemImpl,
+) -> syn::Result<TokenStream2> {
+    let mut invariants = vec![InvariantInfo::parse(attr)?];
+    let mut attrs = Vec::with_capacity(implementation.attrs.len());
+    for attr in std::mem::take(&mut implementation.attrs) {
+        if is_attribute(attr.path(), "invariant") {
+            let tokens = attr.meta.require_list()?.tokens.clone();
+            invariants.push(InvariantInfo::parse(tokens)?);
+        } else {
+            attrs.push(attr);
+        }
+    }
+    implementation.attrs = attrs;
+
+    // The checked value is bound to `this`, as `self` is not available in
+    // constructors
+    let this = mixed_site_ident("this");
+    for invariant in invariants.iter_mut() {
+        for operand in invariant.comparison.operands_mut() {
+            operand.replace_self(&this);
+        }
+    }
+
+    let type_name = tokens_to_string(implementation.self_ty.to_token_stream());
+    let ret = mixed_site_ident("ret");
+    for item in implementation.items.iter_mut() {
+        let syn::ImplItem::Fn(method) = item else {
+            continue;
+        };
+        let name = method.sig.ident.to_string();
+        let checks: Vec<TokenStream2> = invariants
+            .iter()
+            .map(|invariant| invariant.invariant_token_stream(&type_name, &name))
+            .collect();
+
+        let modifies_self = method.sig.receiver().is_some_and(|receiver| {
+            return matches!(&*receiver.ty, syn::Type::Reference(reference) if reference.mutability.is_some());
+        });
+        let check = if modifies_self {
+            let borrows = contains_token(method.sig.output.to_token_stream(), &|token| {
+                return match token {
+                    TokenTree::Punct(punct) => matches!(punct.as_char(), '&' | '\''),
+                    TokenTree::Ident(ident) => ident == "impl",
+                    _ => false,
+                };
+            });
+            if borrows {
+                continue;
+            }
+            quote! {
+                let #this: &Self = &*self;
+                #(#checks)*
+            }
+        } else {
+            match Constructor::from_output(&method.sig.output, &implementation.self_ty) {
+                Some(Constructor::Value) => quote! {
+                    let #this: &Self = &#ret;
+                    #(#checks)*
+                },
+                Some(Constructor::Ok) => quote! {
+                    if let ::core::result::Result::Ok(#this) = &#ret {
+                        #(#checks)*
+                    }
+                },
+                Some(Constructor::Some) => quote! {
+                    if let ::core::option::Option::Some(#this) = &#ret {
+                        #(#checks)*
+                    }
+                },
+                None => continue,
+            }
+        };
+
+        let body = bind_return_value(&method.sig, &method.block, &ret);
+        method.block = syn::parse_quote! {{
+            #body
+            {
+                #check
+            }
+            #ret
+        }};
+    }
+    return Ok(implementation.into_token_stream());
+}
+
+/// How a function without a `&mut self` receiver returns a value whose
+/// invariants are checked.
+enum Constructor {
+    /// The function returns `Self`.
+    Value,
+    /// The function returns `Result<Self, E>`.
+    Ok,
+    /// The function returns `Option<Self>`.
+    Some,
+}
+
+impl Constructor {
+    fn from_output(output: &syn::ReturnType, self_ty: &syn::Type) -> Option<Self> {
+        let is_self = |ty: &syn::Type| {
+            if let syn::Type::Path(path) = ty {
+                if path.qself.is_none() && path.path.is_ident("Self") {
+                    return true;
+                }
+            }
+            return tokens_to_string(ty.to_token_stream())
+                == tokens_to_string(self_ty.to_token_stream());
+        };
+
+        let syn::ReturnType::Type(_, ty) = output else {
+            return None;
+        };
+        if is_self(ty) {
+            return Some(Constructor::Value);
+        }
+        let syn::Type::Path(path) = &**ty else {
+            return None;
+        };
+        let segment = path.path.segments.last()?;
+        let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+            return None;
+        };
+        let Some(syn::GenericArgument::Type(first)) = arguments.args.first() else {
+            return None;
+        };
+        if !is_self(first) {
+            return None;
+        } else if segment.ident == "Result" {
+            return Some(Constructor::Ok);
+        } else if segment.ident == "Option" {
+            return Some(Constructor::Some);
+        } else {
+            return None;
+        }
+    }
+}
+
+/**
+Returns an identifier for a binding within the generated code. Mixed site
+hygiene prevents the binding from shadowing variables of the caller which are
+used in the generated code, e.g. in comparisons, custom messages or error
+expressions.
+ */
+fn mixed_site_ident(name: &str) -> Ident {
+    return Ident::new(name, Span::mixed_site());
+}
+
+/// Returns true if `path` is `name` or `compare_variables::name`.
+fn is_attribute(path: &syn::Path, name: &str) -> bool {
+    if path.is_ident(name) {
+        return true;
+    }
+    let segments: Vec<_> = path.segments.iter().collect();
+    return matches!(
+        segments[..],
+        [krate, attribute] if krate.ident == "compare_variables" && krate.arguments.is_none()
+            && attribute.ident == name && attribute.arguments.is_none()
+    );
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ContractKind {
+    Requires,
+    Ensures,
+}
+
+impl ContractKind {
+    /// Returns the kind of the contract attribute with the given path, if it is one.
+    fn from_path(path: &syn::Path) -> Option<Self> {
+        if is_attribute(path, "requires") {
+            return Some(ContractKind::Requires);
+        } else if is_attribute(path, "ensures") {
+            return Some(ContractKind::Ensures);
+        } else {
+            return None;
+        }
+    }
+}
+
+/// How a violated contract is reported.
+#[derive(Clone, Copy, PartialEq)]
+enum ContractMode {
+    Panic,
+    Result,
+    Debug,
+}
+
+// Parser for the requires and ensures attributes
+struct ContractInfo {
+    kind: ContractKind,
+    comparison: ComparisonErrorInfo,
+    /// The comparison as written in the source code
+    source: String,
+    mode: ContractMode,
+}
+
+impl ContractMode {
+    /// Splits the optional mode `, mode = ...` off the end of `tokens`.
+    fn split(tokens: TokenStream2) -> syn::Result<(TokenStream2, Self)> {
+        let mut tokens: Vec<TokenTree> = tokens.into_iter().collect();
+        let mut mode = ContractMode::Panic;
+        if let [
+            ..,
+            TokenTree::Punct(comma),
+            TokenTree::Ident(key),
+            TokenTree::Punct(eq),
+            TokenTree::Ident(value),
+        ] = &tokens[..]
+        {
+            if comma.as_char() == ',' && key == "mode" && eq.as_char() == '=' {
+                mode = if value == "panic" {
+                    ContractMode::Panic
+                } else if value == "result" {
+                    ContractMode::Result
+                } else if value == "debug" {
+                    ContractMode::Debug
+                } else {
+                    return Err(syn::Error::new(
+                        value.span(),
+                        "expected one of the modes \"panic\", \"result\" or \"debug\"",
+                    ));
+                };
+                tokens.truncate(tokens.len() - 4);
+            }
+        }
+        return Ok((tokens.into_iter().collect(), mode));
+    }
+}
+
+impl ContractInfo {
+    fn parse(kind: ContractKind, tokens: TokenStream2) -> syn::Result<Self> {
+        let (tokens, mode) = ContractMode::split(tokens)?;
+        return Ok(ContractInfo {
+            kind,
+            comparison: syn::parse2(tokens.clone())?,
+            source: tokens_to_string(tokens),
+            mode,
+        });
+    }
+
+    /// Returns the line of the documentation describing the contract.
+    fn doc(&self) -> String {
+        let kind = match self.kind {
+            ContractKind::Requires => "Requires",
+            ContractKind::Ensures => "Ensures",
+        };
+        let consequence = match self.mode {
+            ContractMode::Panic => "panics otherwise",
+            ContractMode::Result => "returns an error otherwise",
+            ContractMode::Debug => "checked by a debug assertion",
+        };
+        return format!(" - {kind} `{}` ({consequence}).", self.source);
+    }
+
+    /// Returns the statement which checks the contract of the function `name`.
+    fn check_token_stream(&self, name: &str) -> TokenStream2 {
+        let binding = mixed_site_ident("comparison");
+        let comparison = self.comparison.as_token_stream();
+        if self.mode == ContractMode::Result {
+            let owned_comparison = if self.comparison.owned {
+                comparison
+            } else {
+                quote! {#comparison.map_err(compare_variables::Comparison::into_owned)}
+            };
+            return quote! {
+                if let Err(#binding) = #owned_comparison {
+                    return Err(::core::convert::From::from(#binding));
+                }
+            };
+        }
+
+        let message = match self.kind {
+            ContractKind::Requires => format!("precondition of `{name}` violated: {{}}"),
+            ContractKind::Ensures => format!("postcondition of `{name}` violated: {{}}"),
+        };
+        let assertion = quote! {
+            if let Err(#binding) = #comparison {
+                panic!(#message, #binding)
+            }
+        };
+        if self.mode == ContractMode::Debug {
+            return quote! {
+                if cfg!(debug_assertions) {
+                    #assertion
+                }
+            };
+        }
+        return assertion;
+    }
+}
+
+// Parser for the invariant attribute
+struct InvariantInfo {
+    comparison: ComparisonErrorInfo,
+    mode: ContractMode,
+}
+
+impl InvariantInfo {
+    fn parse(tokens: TokenStream2) -> syn::Result<Self> {
+        let (tokens, mode) = ContractMode::split(tokens)?;
+        if mode == ContractMode::Result {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "invariants only support the modes \"panic\" and \"debug\"",
+            ));
+        }
+        return Ok(InvariantInfo {
+            comparison: syn::parse2(tokens)?,
+            mode,
+        });
+    }
+
+    /**
+    Returns the statement which checks the invariant of the type `type_name`
+    after the method `name`. The comparison is evaluated for the value bound to
+    `this` (see `Operand::replace_self`).
+     */
+    fn invariant_token_stream(&self, type_name: &str, name: &str) -> TokenStream2 {
+        let binding = mixed_site_ident("comparison");
+        let comparison = self.comparison.as_token_stream();
+        let message = format!("invariant of `{type_name}` violated by `{name}`");
+        let assertion = quote! {
+            if let Err(#binding) = #comparison {
+                panic!("{}", #binding.with_message(#message))
+            }
+        };
+        if self.mode == ContractMode::Debug {
+            return quote! {
+                if cfg!(debug_assertions) {
+                    #assertion
+                }
+            };
+        }
+        return assertion;
+    }
+}
+
+/**
+Defines a newtype whose values are guaranteed to satisfy a comparison.
+
+The syntax is `bounded!(vis Name: Type where comparison)`, where the comparison
+uses the syntax of `compare_variables!` and the placeholder `_` stands for the
+wrapped value. All operands must have the type of the wrapped value.
+Attributes in front of the visibility are attached to the newtype, which
+derives `Debug`, `Clone`, `PartialEq` and `PartialOrd`. Further traits such as
+`Copy` can be derived via such an attribute.
+
+The macro generates:
+- `Name::new` and `TryFrom<Type>`, which return the failed
+`compare_variables::Comparison<Type>` as the error. The placeholder is named
+after the newtype in the comparison.
+- `Name::new_unchecked`, which does not check the comparison.
+- `Name::get` (returns a clone of the value), `Name::into_inner`,
+`Deref<Target = Type>` and `From<Name> for Type`.
+- The arithmetic operators `+`, `-`, `*`, `/` and `%` for a newtype on the left
+side and any value supported by `Type` on the right side. The result is checked
+again and returned as `Result<Name, Comparison<Type>>`. The operators are not
+implemented for a newtype on the right side, therefore the caller has to
+dereference it explicitly (e.g. `a + *b`, see the examples).
+
+With the option `, serde` after the comparison, the newtype is serialized like
+the wrapped value and deserialization fails if the comparison is not true. This
+requires the feature `serde` of `compare_variables`.
+
+# Examples
+
+```
+use compare_variables::{bounded, Comparison};
+
+bounded!(
+    /// A percentage between 0 and 100.
+    #[derive(Copy)]
+    pub Percent: f64 where 0.0 <= _ <= 100.0
+);
+
+let percent = Percent::new(40.0).unwrap();
+assert_eq!(percent.get(), 40.0);
+assert_eq!(*percent / 100.0, 0.4);
+
+let sum = (percent + 50.0).unwrap();
+assert_eq!(sum.get(), 90.0);
+
+let err: Comparison<f64> = (sum + *percent).unwrap_err();
+assert_eq!(
+    err.to_string(),
+    "`0.0 <= Percent (value: 130.0) <= 100.0` is false (failed: `Percent (value: 130.0) <= 100.0`)"
+);
+assert!(Percent::try_from(-1.0).is_err());
+```
+
+Without dereferencing, two newtypes cannot be combined:
+
+```compile_fail
+use compare_variables::bounded;
+
+bounded!(pub Percent: f64 where 0.0 <= _ <= 100.0);
+
+let a = Percent::new(40.0).unwrap();
+let b = Percent::new(50.0).unwrap();
+let _ = a + b;
+```
+ */
+#[proc_macro]
+pub fn bounded(input: TokenStream) -> TokenStream {
+    let bounded_info: BoundedInfo = parse_macro_input!(input);
+    return TokenStream::from(bounded_info.as_token_stream());
+}
+
+// Parser for the bounded macro
+struct BoundedInfo {
+    attrs: Vec<syn::Attribute>,
+    vis: syn::Visibility,
+    name: Ident,
+    ty: syn::Type,
+    /// The comparison with the placeholder replaced by `value`
+    comparison: ComparisonErrorInfo,
+    /// The comparison as written in the source code
+    source: String,
+    value: Ident,
+    serde: bool,
+}
+
+impl Parse for BoundedInfo {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let attrs = input.call(syn::Attribute::parse_outer)?;
+        let vis = input.parse()?;
+        let name: Ident = input.parse()?;
+        input.parse::<Token![:]>()?;
+        let ty = input.parse()?;
+        input.parse::<Token![where]>()?;
+
+        // The optional `, serde` is at the end of the tokens
+        let mut tokens: Vec<TokenTree> = input.parse::<TokenStream2>()?.into_iter().collect();
+        let mut serde = false;
+        if let [.., TokenTree::Punct(comma), TokenTree::Ident(option)] = &tokens[..] {
+            if comma.as_char() == ',' && option == "serde" {
+                serde = true;
+                tokens.truncate(tokens.len() - 2);
+            }
+        }
+        let tokens: TokenStream2 = tokens.into_iter().collect();
+        let source = tokens_to_string(tokens.clone());
+        let mut comparison: ComparisonErrorInfo = syn::parse2(tokens)?;
+
+        let value = mixed_site_ident("value");
+        for operand in comparison.operands_mut() {
+            operand.replace_placeholder(&value, &name);
+        }
+        return Ok(BoundedInfo {
+            attrs,
+            vis,
+            name,
+            ty,
+            comparison,
+            source,
+            value,
+            serde,
+        });
+    }
+}
+
+impl BoundedInfo {
+    fn as_token_stream(&self) -> TokenStream2 {
+        let BoundedInfo {
+            attrs,
+            vis,
+            name,
+            ty,
+            value,
+            ..
+        } = self;
+        let comparison = self.comparison.as_token_stream();
+        let comparison = if self.comparison.owned {
+            comparison
+        } else {
+            quote! {#comparison.map_err(compare_variables::Comparison::into_owned)}
+        };
+        let new_doc = format!(
+            " Returns a new value if `{}` is true for `_ = value` and the failed comparison otherwise.",
+            self.source
+        );
+        let unchecked_doc = format!(
+            " Returns a new value without checking whether `{}` is true for `_ = value`.",
+            self.source
+        );
+
+        let operators = [
+            (quote! {Add}, quote! {add}, quote! {+}),
+            (quote! {Sub}, quote! {sub}, quote! {-}),
+            (quote! {Mul}, quote! {mul}, quote! {*}),
+            (quote! {Div}, quote! {div}, quote! {/}),
+            (quote! {Rem}, quote! {rem}, quote! {%}),
+        ]
+        .into_iter()
+        .map(|(op, method, symbol)| {
+            return quote! {
+                impl<Rhs> ::core::ops::#op<Rhs> for #name
+                where
+                    #ty: ::core::ops::#op<Rhs, Output = #ty>,
+                {
+                    type Output = ::core::result::Result<#name, compare_variables::Comparison<#ty>>;
+
+                    fn #method(self, rhs: Rhs) -> Self::Output {
+                        return #name::new(self.0 #symbol rhs);
+                    }
+                }
+            };
+        });
+
+        let serde = if self.serde {
+            quote! {
+                impl compare_variables::serde::Serialize for #name {
+                    fn serialize<S: compare_variables::serde::Serializer>(
+                        &self,
+                        serializer: S,
+                    ) -> ::core::result::Result<S::Ok, S::Error> {
+                        return compare_variables::serde::Serialize::serialize(&self.0, serializer);
+                    }
+                }
+
+                impl<'de> compare_variables::serde::Deserialize<'de> for #name {
+                    fn deserialize<D: compare_variables::serde::Deserializer<'de>>(
+                        deserializer: D,
+                    ) -> ::core::result::Result<Self, D::Error> {
+                        let #value = <#ty as compare_variables::serde::Deserialize<'de>>::deserialize(deserializer)?;
+                        return #name::new(#value).map_err(<D::Error as compare_variables::serde::de::Error>::custom);
+                    }
+                }
+            }
+        } else {
+            quote! {}
+        };
+
+        return quote! {
+            #(#attrs)*
+            #[derive(Debug, Clone, PartialEq, PartialOrd)]
+            #vis struct #name(#ty);
+
+            impl #name {
+                #[doc = #new_doc]
+                #vis fn new(#value: #ty) -> ::core::result::Result<Self, compare_variables::Comparison<#ty>> {
+                    #comparison?;
+                    return Ok(#name(#value));
+                }
+
+                #[doc = #unchecked_doc]
+                #vis fn new_unchecked(#value: #ty) -> Self {
+                    return #name(#value);
+                }
+
+                /// Returns a clone of the wrapped value.
+                #vis fn get(&self) -> #ty {
+                    return ::core::clone::Clone::clone(&self.0);
+                }
+
+                /// Returns the wrapped value.
+                #vis fn into_inner(self) -> #ty {
+                    return self.0;
+                }
+            }
+
+            impl ::core::convert::TryFrom<#ty> for #name {
+                type Error = compare_variables::Comparison<#ty>;
+
+                fn try_from(#value: #ty) -> ::core::result::Result<Self, Self::Error> {
+                    return #name::new(#value);
+                }
+            }
+
+            impl ::core::convert::From<#name> for #ty {
+                fn from(#value: #name) -> Self {
+                    return #value.0;
+                }
+            }
+
+            impl ::core::ops::Deref for #name {
+                type Target = #ty;
+
+                fn deref(&self) -> &Self::Target {
+                    return &self.0;
+                }
+            }
+
+            #(#operators)*
+
+            #serde
+        };
+    }
+}
+
+/**
+Compares primitive values in const contexts.
+
+This is the const counterpart to `compare_variables!` for the primitive integer
+types and `bool`. The syntax is the same, except that approximate comparisons
+and range membership are not supported. The macro returns `Ok(())` if the
+comparison is true and the message of the failed comparison as a
+`compare_variables::ConstMessage` otherwise. The message is the same as the one
+of the equivalent `compare_variables::Comparison` in the default
+`compare_variables::ExpressionStyle`.
+
+All operands are evaluated once and must have the same type. If all operands
+are unsuffixed integer literals, they are compared as `i32`.
+
+# Examples
+
+```
+use compare_variables::const_compare;
+
+const MAX: u32 = 64;
+const VALID: bool = const_compare!(5i32 >= -1i32).is_ok();
+assert!(VALID);
+
+const fn check(len: u32) -> bool {
+    return const_compare!(0 < len <= MAX).is_ok();
+}
+assert!(check(64));
+assert!(!check(65));
+
+let err = const_compare!(0 < 65u32 as len <= MAX).unwrap_err();
+assert_eq!(
+    err.as_str(),
+    "`0 < len (value: 65) <= MAX (value: 64)` is false (failed: `len (value: 65) <= MAX (value: 64)`)"
+);
+```
+ */
+#[proc_macro]
+pub fn const_compare(input: TokenStream) -> TokenStream {
+    let const_info: ConstCompareInfo = parse_macro_input!(input);
+    return TokenStream::from(const_info.as_token_stream());
+}
+
+/**
+Asserts a comparison of primitive values at compile time.
+
+The comparison is evaluated by `const_compare!` within an inline `const` block.
+If it is not true, compilation fails with the message of the comparison (see
+`const_compare!`). The macro expands to an expression of type `()`, which can
+use const generic parameters when used within a function. In that case, the
+assertion is checked for every instantiation of the function. Outside of
+functions, the expression is used as the value of an unnamed constant.
+
+# Examples
+
+```
+use compare_variables::static_assert_compare;
+
+const BUFFER_SIZE: usize = 48;
+const _: () = static_assert_compare!(16 <= BUFFER_SIZE <= 64);
+
+fn bits<const N: usize>() -> [bool; N] {
+    static_assert_compare!(N <= 64);
+    return [false; N];
+}
+
+assert_eq!(bits::<8>().len(), 8);
+```
+
+```compile_fail
+use compare_variables::static_assert_compare;
+
+const BUFFER_SIZE: usize = 128;
+// error: `16 <= BUFFER_SIZE (value: 128) <= 64` is false (failed: `BUFFER_SIZE (value: 128) <= 64`)
+const _: () = static_assert_compare!(16 <= BUFFER_SIZE <= 64);
+```
+ */
+#[proc_macro]
+pub fn static_assert_compare(input: TokenStream) -> TokenStream {
+    let const_info: ConstCompareInfo = parse_macro_input!(input);
+    let comparison = const_info.as_token_stream();
+    let binding = mixed_site_ident("message");
+    return TokenStream::from(quote! {
+        const {
+            if let Err(#binding) = #comparison {
+                panic!("{}", #binding.as_str());
+            }
+        }
+    });
+}
+
+// Parser for the const_compare and static_assert_compare macros
+struct ConstCompareInfo {
+    comparison: ComparisonErrorInfo,
+}
+
+impl Parse for ConstCompareInfo {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let comparison: ComparisonErrorInfo = input.parse()?;
+        if comparison.range.is_some() {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "range membership is not supported in const contexts",
+            ));
+        }
+        if comparison.tolerance.is_some()
+            || comparison
+                .links
+                .iter()
+                .any(|(operator, _)| matches!(operator, Operator::ApproxEqual))
+        {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "approximate comparisons are not supported in const contexts",
+            ));
+        }
+        return Ok(ConstCompareInfo { comparison });
+    }
+}
+
+impl ConstCompareInfo {
+    /// Returns the operands of the comparison chain.
+    fn operands(&self) -> Vec<&Operand> {
+        let mut operands = vec![&self.comparison.first_arg];
+        operands.extend(self.comparison.links.iter().map(|(_, operand)| operand));
+        return operands;
+    }
+
+    /// Returns true if all operands are integer literals without a suffix.
+    fn is_unsuffixed_literal(&self) -> bool {
+        fn is_unsuffixed(expr: &syn::Expr) -> bool {
+            match expr {
+                syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Int(int),
+                    ..
+                }) => return int.suffix().is_empty(),
+                syn::Expr::Unary(syn::ExprUnary {
+                    op: syn::UnOp::Neg(_),
+                    expr,
+                    ..
+                }) => return is_unsuffixed(expr),
+                syn::Expr::Group(group) => return is_unsuffixed(&group.expr),
+                syn::Expr::Paren(paren) => return is_unsuffixed(&paren.expr),
+                _ => return false,
+            }
+        }
+        return self.operands().into_iter().all(|operand| {
+            return syn::parse2::<syn::Expr>(operand.value_tokens())
+                .is_ok_and(|expr| is_unsuffixed(&expr));
+        });
+    }
+
+    /**
+    Returns the expression which evaluates the comparison, wrapped in `Ok` if it
+    is true and the message in `Err` otherwise.
+     */
+    fn as_token_stream(&self) -> TokenStream2 {
+        let values = mixed_site_ident("values");
+        let links = mixed_site_ident("links");
+        let message = mixed_site_ident("message");
+        let operands = self.operands();
+        let count = operands.len();
+
+        // The values are stored in an array, so all operands have the same type
+        // before the inherent methods of `ConstValue` are resolved.
+        let value_tokens = operands.iter().map(|operand| operand.value_tokens());
+        let values_type = if self.is_unsuffixed_literal() {
+            quote! {: [i32; #count]}
+        } else {
+            quote! {}
+        };
+
+        let link_outcomes = self.comparison.links.iter().enumerate().map(|(index, (operator, _))| {
+            let operator = operator.as_token_stream();
+            let next = index + 1;
+            return quote! {
+                #operator.is_true_for_ordering(compare_variables::ConstValue(#values[#index]).ordering(#values[#next]))
+            };
+        });
+        let link_indices: Vec<usize> = (0..self.comparison.links.len()).collect();
+
+        // A value is displayed as `name (value: ...)` or only as the value,
+        // like `ComparisonValue` does.
+        let names: Vec<Option<String>> = operands.iter().map(|operand| operand.name()).collect();
+        let write_value = |index: usize| {
+            let value =
+                quote! {compare_variables::ConstValue(#values[#index]).write(&mut #message);};
+            match &names[index] {
+                Some(name) => {
+                    let prefix = format!("{name} (value: ");
+                    return quote! {
+                        #message.push_str(#prefix);
+                        #value
+                        #message.push_str(")");
+                    };
+                }
+                None => return value,
+            }
+        };
+        // The longest value (i128::MIN) has 40 characters
+        let value_capacity: Vec<usize> = names
+            .iter()
+            .map(|name| name.as_ref().map_or(0, |name| name.len() + 10) + 40)
+            .collect();
+
+        let mut capacity = 16;
+        let mut expression = vec![quote! {#message.push_str("`");}, write_value(0)];
+        capacity += value_capacity[0];
+        for (index, (operator, _)) in self.comparison.links.iter().enumerate() {
+            let separator = format!(" {} ", operator.as_str());
+            expression.push(quote! {#message.push_str(#separator);});
+            expression.push(write_value(index + 1));
+            capacity += separator.len() + value_capacity[index + 1];
+        }
+        expression.push(quote! {#message.push_str("` is false");});
+
+        // Highlight the failed links of chained comparisons
+        let mut failed = Vec::new();
+        if self.comparison.links.len() > 1 {
+            failed.push(quote! {#message.push_str(" (failed: ");});
+            for (index, (operator, _)) in self.comparison.links.iter().enumerate() {
+                let previous = &link_indices[..index];
+                let separator = format!(" {} ", operator.as_str());
+                let left = write_value(index);
+                let right = write_value(index + 1);
+                failed.push(quote! {
+                    if !#links[#index] {
+                        if !(true #(&& #links[#previous])*) {
+                            #message.push_str(", ");
+                        }
+                        #message.push_str("`");
+                        #left
+                        #message.push_str(#separator);
+                        #right
+                        #message.push_str("`");
+                    }
+                });
+                capacity += 4 + separator.len() + value_capacity[index] + value_capacity[index + 1];
+            }
+            failed.push(quote! {#message.push_str(")");});
+        }
+
+        return quote! {
+            {
+                let #values #values_type = [#(#value_tokens),*];
+                let #links = [#(#link_outcomes),*];
+                if true #(&& #links[#link_indices])* {
+                    ::core::result::Result::<(), compare_variables::ConstMessage<#capacity>>::Ok(())
+                } else {
+                    let mut #message = compare_variables::ConstMessage::<#capacity>::new();
+                    #(#expression)*
+                    #(#failed)*
+                    ::core::result::Result::Err(#message)
+                }
+            }
+        };
+    }
+}
+
+#[repr(u8)]
+enum Operator {
+    Lesser,
+    LesserOrEqual,
+    Equal,
+    Inequal,
+    GreaterOrEqual,
+    Greater,
+    ApproxEqual,
+}
+
+impl Operator {
+    /// Returns true if the next tokens of `input` form a comparison operator.
+    fn peek(input: syn::parse::ParseStream) -> bool {
+        return input.peek(Token![<=])
+            || input.peek(Token![>=])
+            || input.peek(Token![==])
+            || input.peek(Token![!=])
+            || input.peek(Token![<])
+            || input.peek(Token![>])
+            || (input.peek(Token![~]) && input.peek2(Token![=]));
+    }
+
+    /// Returns the operator as written in source code.
+    fn as_str(&self) -> &'static str {
+        match self {
+            Operator::Lesser => return "<",
+            Operator::LesserOrEqual => return "<=",
+            Operator::Equal => return "==",
+            Operator::Inequal => return "!=",
+            Operator::GreaterOrEqual => return ">=",
+            Operator::Greater => return ">",
+            Operator::ApproxEqual => return "~=",
+        }
+    }
+
+    fn as_token_stream(&self) -> proc_macro2::TokenStream {
+        match self {
+            Operator::Lesser => {
+                quote! {
+                    compare_variables::ComparisonOperator::Lesser
+                }
+            }
+            Operator::LesserOrEqual => {
+                quote! {
+                    compare_variables::ComparisonOperator::LesserOrEqual
+                }
+            }
+            Operator::Equal => {
+                quote! {
+                    compare_variables::ComparisonOperator::Equal
+                }
+            }
+            Operator::Inequal => {
+                quote! {
+                    compare_variables::ComparisonOperator::Inequal
+                }
+            }
+            Operator::GreaterOrEqual => {
+                quote! {
+                    compare_variables::ComparisonOperator::GreaterOrEqual
+                }
+            }
+            Operator::Greater => {
+                quote! {
+                    compare_variables::ComparisonOperator::Greater
+                }
+            }
+            Operator::ApproxEqual => {
+                unreachable!("approximate comparisons are created via Comparison::new_approx")
+            }
+        }
+    }
+}
+
+impl Parse for Operator {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // If Token![<] is tested before Token![<=], then "<" is parsed, leaving only
+        // "=". This will then lead to a compile error.
+        if input.peek(Token![<=]) {
+            input.parse::<Token![<=]>()?;
+            Ok(Operator::LesserOrEqual)
+        } else if input.peek(Token![>=]) {
+            input.parse::<Token![>=]>()?;
+            Ok(Operator::GreaterOrEqual)
+        } else if input.peek(Token![==]) {
+            input.parse::<Token![==]>()?;
+            Ok(Operator::Equal)
+        } else if input.peek(Token![!=]) {
+            input.parse::<Token![!=]>()?;
+            Ok(Operator::Inequal)
+        } else if input.peek(Token![<]) {
+            input.parse::<Token![<]>()?;
+            Ok(Operator::Lesser)
+        } else if input.peek(Token![>]) {
+            input.parse::<Token![>]>()?;
+            Ok(Operator::Greater)
+        } else if input.peek(Token![~]) && input.peek2(Token![=]) {
+            input.parse::<Token![~]>()?;
+            input.parse::<Token![=]>()?;
+            Ok(Operator::ApproxEqual)
+        } else {
+            Err(syn::Error::new(
+                input.span(),
+                "no comparison operator could be identified. Valid
+                    operators are \"<\", \"<=\", \"==\", \"!=\", \">=\", \">\" or \"~=\".",
+            ))
+        }
+    }
+}
+
+/// Tolerance of the approximate equality operator `~=`, e.g. `abs = 1e-9`.
+enum Tolerance {
+    Absolute(syn::Expr),
+    Relative(syn::Expr),
+    Ulps(syn::Expr),
+}
+
+impl Tolerance {
+    /// Returns true if the next tokens of `input` are `, abs =`, `, rel =` or `, ulps =`.
+    fn peek(input: syn::parse::ParseStream) -> bool {
+        if !input.peek(Token![,]) || !input.peek3(Token![=]) {
+            return false;
+        }
+        let fork = input.fork();
+        let _ = fork.parse::<Token![,]>();
+        return match fork.parse::<Ident>() {
+            Ok(ident) => ident == "abs" || ident == "rel" || ident == "ulps",
+            Err(_) => false,
+        };
+    }
+
+    fn as_token_stream(&self) -> proc_macro2::TokenStream {
+        match self {
+            Tolerance::Absolute(value) => {
+                quote! {
+                    compare_variables::Tolerance::Absolute(#value)
+                }
+            }
+            Tolerance::Relative(value) => {
+                quote! {
+                    compare_variables::Tolerance::Relative(#value)
+                }
+            }
+            Tolerance::Ulps(value) => {
+                quote! {
+                    compare_variables::Tolerance::Ulps(#value)
+                }
+            }
+        }
+    }
+}
+
+impl Parse for Tolerance {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![,]>()?;
+        let kind: Ident = input.parse()?;
+        input.parse::<Token![=]>()?;
+
+        // The tolerance ends at the first `,`, `=>` or logical operator
+        let mut tokens = TokenStream2::new();
+        while !input.is_empty()
+            && !input.peek(Token![,])
+            && !input.peek(Token![=>])
+            && !peek_logical_operator(input)
+        {
+            input.parse::<TokenTree>()?.to_tokens(&mut tokens);
+        }
+        let value: syn::Expr = syn::parse2(tokens)?;
+        if kind == "abs" {
+            return Ok(Tolerance::Absolute(value));
+        } else if kind == "rel" {
+            return Ok(Tolerance::Relative(value));
+        } else if kind == "ulps" {
+            return Ok(Tolerance::Ulps(value));
+        } else {
+            return Err(syn::Error::new(
+                kind.span(),
+                "expected one of the tolerances \"abs\", \"rel\" or \"ulps\"",
+            ));
+        }
+    }
+}
+
+/// The range of a membership test `x in range`.
+enum Range {
+    /// A range expression such as `lo..=hi`, `..hi` or `lo..`.
+    Bounds {
+        start: Option<Operand>,
+        end: Option<Operand>,
+        inclusive: bool,
+    },
+    /// Any other expression whose type implements `RangeBounds`.
+    Value(Operand),
+}
+
+impl Range {
+    fn bound_token_stream(
+        bound: Option<&Operand>,
+        inclusive: bool,
+        owned: bool,
+    ) -> proc_macro2::TokenStream {
+        match bound {
+            Some(operand) => {
+                let operand = operand.as_token_stream(owned);
+                if inclusive {
+                    quote! {std::ops::Bound::Included(#operand)}
+                } else {
+                    quote! {std::ops::Bound::Excluded(#operand)}
+                }
+            }
+            None => quote! {std::ops::Bound::Unbounded},
+        }
+    }
+}
+
+impl Parse for Range {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        input.parse::<Token![in]>()?;
+        let start = if input.peek(Token![..]) {
+            None
+        } else {
+            let operand = Operand::parse(input)?;
+            if !input.peek(Token![..]) {
+                return Ok(Range::Value(operand));
+            }
+            Some(operand)
+        };
+
+        let span = input.span();
+        let inclusive = if input.peek(Token![..=]) {
+            input.parse::<Token![..=]>()?;
+            true
+        } else {
+            input.parse::<Token![..]>()?;
+            false
+        };
+        let end = if input.is_empty()
+            || input.peek(Token![,])
+            || input.peek(Token![=>])
+            || peek_logical_operator(input)
+        {
+            None
+        } else {
+            Some(Operand::parse(input)?)
+        };
+        if inclusive && end.is_none() {
+            return Err(syn::Error::new(
+                span,
+                "inclusive ranges must have an end bound",
+            ));
+        }
+
+        return Ok(Range::Bounds {
+            start,
+            end,
+            inclusive,
+        });
+    }
+}
+
+/// How an operand is represented in the message of the comparison.
+enum DisplayName {
+    /// Only the value is displayed (literals and operands prefixed with `val`).
+    Hidden,
+    /// The source code of the operand is displayed next to its value.
+    Source,
+    /// A user-defined alias (given via `as`) is displayed next to the value.
+    Alias(String),
+    /// The path of a field within the value validated by `#[derive(Validate)]`,
+    /// which is prefixed at runtime by the path of the value (`path`).
+    Field { path: Ident, name: String },
+}
+
+/// A single operand of the comparison. This can be any Rust expression, e.g.
+/// a literal, a variable, a field access or a method call.
+struct Operand {
+    tokens: TokenStream2,
+    display_name: DisplayName,
+}
+
+impl Operand {
+    /**
+    Returns the tokens of the operand expression. Parentheses or braces around
+    the entire expression are needed within the macro to separate the
+    expression from the comparison operators, but would trigger the
+    `unused_parens` / `unused_braces` lints in the expanded code.
+     */
+    fn value_tokens(&self) -> TokenStream2 {
+        let mut iter = self.tokens.clone().into_iter();
+        if let (Some(TokenTree::Group(group)), None) = (iter.next(), iter.next()) {
+            match group.delimiter() {
+                Delimiter::Parenthesis => return group.stream(),
+                Delimiter::Brace => {
+                    let inner = group.stream();
+                    return quote! {{ #inner }};
+                }
+                _ => (),
+            }
+        }
+        return self.tokens.clone();
+    }
+
+    /**
+    Replaces the placeholder `_` with `field` (e.g. `self.ratio`). Afterwards,
+    operands which are fields of `self` are named by their path relative to
+    `self`, which is prefixed at runtime by the contents of the variable `path`.
+     */
+    fn resolve_fields(&mut self, field: &TokenStream2, path: &Ident) {
+        /// Returns the path of the field if `expr` is a field of `self`.
+        fn field_path(expr: &syn::Expr) -> Option<Vec<String>> {
+            match expr {
+                syn::Expr::Field(field) => {
+                    let mut path = match &*field.base {
+                        syn::Expr::Path(base) if base.path.is_ident("self") => Vec::new(),
+                        base => field_path(base)?,
+                    };
+                    match &field.member {
+                        syn::Member::Named(ident) => path.push(ident.to_string()),
+                        syn::Member::Unnamed(index) => path.push(index.index.to_string()),
+                    }
+                    return Some(path);
+                }
+                syn::Expr::Group(group) => return field_path(&group.expr),
+                syn::Expr::Paren(paren) => return field_path(&paren.expr),
+                _ => return None,
+            }
+        }
+
+        self.tokens = replace_ident(self.tokens.clone(), "_", field);
+        if let DisplayName::Source = self.display_name {
+            if let Ok(expr) = syn::parse2::<syn::Expr>(self.tokens.clone()) {
+                if let Some(name) = field_path(&expr) {
+                    self.display_name = DisplayName::Field {
+                        path: path.clone(),
+                        name: name.join("."),
+                    };
+                }
+            }
+        }
+    }
+
+    /**
+    Replaces `self` with `this` (used for checking invariants of values which
+    are not available as `self`). The operand keeps the name it had before,
+    e.g. `self.lo`.
+     */
+    fn replace_self(&mut self, this: &Ident) {
+        if let DisplayName::Source = self.display_name {
+            self.display_name = DisplayName::Alias(tokens_to_string(self.tokens.clone()));
+        }
+        self.tokens = replace_ident(self.tokens.clone(), "self", &quote! {#this});
+    }
+
+    /**
+    Replaces the placeholder `_` with `value`. Within the name of the operand,
+    the placeholder is replaced by `name` instead, e.g. `Percent`.
+     */
+    fn replace_placeholder(&mut self, value: &Ident, name: &Ident) {
+        if let DisplayName::Source = self.display_name {
+            let named = replace_ident(self.tokens.clone(), "_", &quote! {#name});
+            self.display_name = DisplayName::Alias(tokens_to_string(named));
+        }
+        self.tokens = replace_ident(self.tokens.clone(), "_", &quote! {#value});
+    }
+
+    /// Returns true if borrowing the operand borrows a temporary value.
+    fn is_temporary(&self) -> bool {
+        return match syn::parse2::<syn::Expr>(self.value_tokens()) {
+            Ok(expr) => !is_place_or_literal(&expr),
+            Err(_) => true,
+        };
+    }
+
+    /// Returns the name of the operand as an `Option<&'static str>`. Names of
+    /// fields are created at runtime, see `field_path_token_stream`.
+    fn name_token_stream(&self) -> proc_macro2::TokenStream {
+        match &self.display_name {
+            DisplayName::Hidden | DisplayName::Field { .. } => quote! {None},
+            DisplayName::Source => {
+                let source = tokens_to_string(self.tokens.clone());
+                quote! {Some(#source)}
+            }
+            DisplayName::Alias(alias) => quote! {Some(#alias)},
+        }
+    }
+
+    /// Returns the name of the operand as a `Cow<'static, str>`, if it is a
+    /// field whose name is joined with the path at runtime.
+    fn field_path_token_stream(&self) -> Option<proc_macro2::TokenStream> {
+        match &self.display_name {
+            DisplayName::Field { path, name } => {
+                return Some(quote! {compare_variables::field_path(#path, #name)});
+            }
+            _ => return None,
+        }
+    }
+
+    /// Returns the name of the operand in the message, if it has one.
+    fn name(&self) -> Option<String> {
+        match &self.display_name {
+            DisplayName::Hidden => return None,
+            DisplayName::Source => return Some(tokens_to_string(self.tokens.clone())),
+            DisplayName::Alias(alias) => return Some(alias.clone()),
+            DisplayName::Field { name, .. } => return Some(name.clone()),
+        }
+    }
+
+    /// Returns the operand wrapped in a `ComparisonValue`. Unless `owned` is
+    /// true, the operand is borrowed instead of being moved.
+    fn as_token_stream(&self, owned: bool) -> proc_macro2::TokenStream {
+        let tokens = self.value_tokens();
+        let value = if owned {
+            quote! {#tokens}
+        } else {
+            quote! {&(#tokens)}
+        };
+        if let Some(path) = self.field_path_token_stream() {
+            return quote! {
+                compare_variables::ComparisonValue::named(#value, #path)
+            };
+        }
+        let name = self.name_token_stream();
+        return quote! {
+            compare_variables::ComparisonValue::new(#value, #name)
+        };
+    }
+}
+
+/// Replaces every occurrence of the identifier `name` in `tokens` with
+/// `replacement`, including the occurrences within groups.
+fn replace_ident(tokens: TokenStream2, name: &str, replacement: &TokenStream2) -> TokenStream2 {
+    return tokens
+        .into_iter()
+        .map(|token| match token {
+            TokenTree::Ident(ident) if ident == name => {
+                return TokenTree::Group(proc_macro2::Group::new(
+                    Delimiter::None,
+                    replacement.clone(),
+                ));
+            }
+            TokenTree::Group(group) => {
+                let mut replaced = proc_macro2::Group::new(
+                    group.delimiter(),
+                    replace_ident(group.stream(), name, replacement),
+                );
+                replaced.set_span(group.span());
+                return TokenTree::Group(replaced);
+            }
+            token => return token,
+        })
+        .collect();
+}
+
+/**
+Formats the tokens of an operand as they would usually be written in source
+code, e.g. `v.len()` instead of `v . len ()` (which is what
+`TokenStream::to_string` returns).
+ */
+fn tokens_to_string(tokens: TokenStream2) -> String {
+    fn is_keyword(ident: &Ident) -> bool {
+        return matches!(
+            ident.to_string().as_str(),
+            "as" | "in" | "if" | "else" | "match" | "return" | "move" | "mut" | "ref"
+        );
+    }
+
+    let mut out = String::new();
+    let mut prev: Option<TokenTree> = None;
+    let mut prev_is_unary = false;
+    let mut prev_is_path_sep = false;
+    let mut generic_depth = 0usize;
+
+    for token in tokens {
+        let mut is_unary = false;
+        let mut is_path_sep = false;
+        let space = match (&prev, &token) {
+            (None, _) => false,
+            (Some(_), _) if prev_is_unary || prev_is_path_sep => false,
+            // Multi-character operators such as "<=" or "::" and lifetimes
+            (Some(TokenTree::Punct(p)), TokenTree::Punct(_)) if p.spacing() == Spacing::Joint => {
+                false
+            }
+            (Some(TokenTree::Punct(p)), _) if p.as_char() == '\'' => false,
+            // Field access, method calls and ranges
+            (Some(TokenTree::Punct(p)), _) if p.as_char() == '.' => false,
+            (_, TokenTree::Punct(p)) if matches!(p.as_char(), '.' | ',' | ';' | '?' | ':') => false,
+            // Generic arguments of a turbofish
+            (_, TokenTree::Punct(p)) if generic_depth > 0 && matches!(p.as_char(), '<' | '>') => {
+                false
+            }
+            (Some(TokenTree::Punct(p)), _) if generic_depth > 0 && p.as_char() == '<' => false,
+            // Macro invocations
+            (Some(TokenTree::Ident(i)), TokenTree::Punct(p))
+                if p.as_char() == '!' && !is_keyword(i) =>
+            {
+                false
+            }
+            // Function calls and indexing
+            (Some(prev), TokenTree::Group(g))
+                if matches!(g.delimiter(), Delimiter::Parenthesis | Delimiter::Bracket) =>
+            {
+                match prev {
+                    TokenTree::Ident(i) => is_keyword(i),
+                    TokenTree::Group(_) => false,
+                    TokenTree::Punct(p) => !matches!(p.as_char(), '!' | '>'),
+                    TokenTree::Literal(_) => true,
+                }
+            }
+            _ => true,
+        };
+
+        if let TokenTree::Punct(p) = &token {
+            match p.as_char() {
+                // A unary operator is not followed by a space.
+                '-' | '!' | '&' | '*' => {
+                    is_unary = match &prev {
+                        None => true,
+                        Some(TokenTree::Punct(prev)) => {
+                            prev.spacing() == Spacing::Alone || prev.as_char() == p.as_char()
+                        }
+                        Some(TokenTree::Ident(i)) => is_keyword(i),
+                        _ => false,
+                    }
+                }
+                ':' => {
+                    if let Some(TokenTree::Punct(prev)) = &prev {
+                        is_path_sep = prev.as_char() == ':' && prev.spacing() == Spacing::Joint;
+                    }
+                    is_path_sep = is_path_sep || p.spacing() == Spacing::Joint;
+                }
+                '<' if prev_is_path_sep || generic_depth > 0 => generic_depth += 1,
+                '>' if generic_depth > 0 => generic_depth -= 1,
+                _ => (),
+            }
+        }
+
+        if space {
+            out.push(' ');
+        }
+        match &token {
+            TokenTree::Group(g) => {
+                let inner = tokens_to_string(g.stream());
+                match g.delimiter() {
+                    Delimiter::Parenthesis => out.push_str(&format!("({inner})")),
+                    Delimiter::Bracket => out.push_str(&format!("[{inner}]")),
+                    Delimiter::Brace if inner.is_empty() => out.push_str("{}"),
+                    Delimiter::Brace => out.push_str(&format!("{{ {inner} }}")),
+                    Delimiter::None => out.push_str(&inner),
+                }
+            }
+            other => out.push_str(&other.to_string()),
+        }
+
+        prev_is_unary = is_unary;
+        prev_is_path_sep = is_path_sep;
+        prev = Some(token);
+    }
+    return out;
+}
+
+/// Returns true if the next tokens of `input` end the current operand.
+fn peek_operand_end(input: syn::parse::ParseStream) -> bool {
+    return input.is_empty()
+        || input.peek(Token![,])
+        || input.peek(Token![as])
+        || input.peek(Token![in])
+        || input.peek(Token![..])
+        || input.peek(Token![=>])
+        || Operator::peek(input);
+}
+
+/// Returns true if the next tokens of `input` are `&&` or `||`.
+fn peek_logical_operator(input: syn::parse::ParseStream) -> bool {
+    return input.peek(Token![&&]) || input.peek(Token![||]);
+}
+
+/**
+Collects the tokens of a single operand. The operand ends at the first
+comparison operator, logical operator, `as` or `,` which is not enclosed in a
+group (e.g. parentheses) or in the generic arguments of a turbofish (`::<...>`).
+ */
+fn parse_operand_tokens(input: syn::parse::ParseStream) -> syn::Result<TokenStream2> {
+    let mut tokens = TokenStream2::new();
+    let mut generic_depth = 0usize;
+    while !input.is_empty() {
+        if generic_depth > 0 {
+            // Inside the generic arguments of a turbofish, "<" and ">" are
+            // brackets and not comparison operators.
+            if input.peek(Token![->]) {
+                input.parse::<Token![->]>()?.to_tokens(&mut tokens);
+                continue;
+            } else if input.peek(Token![<]) {
+                generic_depth += 1;
+            } else if input.peek(Token![>]) {
+                generic_depth -= 1;
+            }
+            input.parse::<TokenTree>()?.to_tokens(&mut tokens);
+            continue;
+        }
+
+        // Multi-character punctuation containing "<" or ">" which is not a
+        // comparison operator
+        if input.peek(Token![->]) {
+            input.parse::<Token![->]>()?.to_tokens(&mut tokens);
+        } else if input.peek(Token![<<=]) {
+            input.parse::<Token![<<=]>()?.to_tokens(&mut tokens);
+        } else if input.peek(Token![>>=]) {
+            input.parse::<Token![>>=]>()?.to_tokens(&mut tokens);
+        } else if input.peek(Token![<<]) {
+            input.parse::<Token![<<]>()?.to_tokens(&mut tokens);
+        } else if input.peek(Token![>>]) {
+            input.parse::<Token![>>]>()?.to_tokens(&mut tokens);
+        } else if input.peek(Token![::]) {
+            input.parse::<Token![::]>()?.to_tokens(&mut tokens);
+            if input.peek(Token![<]) {
+                input.parse::<Token![<]>()?.to_tokens(&mut tokens);
+                generic_depth = 1;
+            }
+        } else if peek_operand_end(input) || (!tokens.is_empty() && peek_logical_operator(input)) {
+            break;
+        } else {
+            input.parse::<TokenTree>()?.to_tokens(&mut tokens);
+        }
+    }
+
+    if tokens.is_empty() {
+        return Err(input.error("expected an expression"));
+    }
+    return Ok(tokens);
+}
+
+/// Returns true if the expression is a (possibly negated) literal.
+fn is_literal(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Lit(_) => return true,
+        syn::Expr::Unary(unary) => {
+            return matches!(unary.op, syn::UnOp::Neg(_)) && is_literal(&unary.expr);
+        }
+        syn::Expr::Group(group) => return is_literal(&group.expr),
+        _ => return false,
+    }
+}
+
+/**
+Returns true if the expression is a place expression (e.g. a variable or a
+field access) or a literal. Borrowing such an expression does not borrow a
+temporary value.
+ */
+fn is_place_or_literal(expr: &syn::Expr) -> bool {
+    match expr {
+        syn::Expr::Path(_) => return true,
+        syn::Expr::Field(field) => return is_place_or_literal(&field.base),
+        syn::Expr::Index(index) => return is_place_or_literal(&index.expr),
+        syn::Expr::Paren(paren) => return is_place_or_literal(&paren.expr),
+        syn::Expr::Group(group) => return is_place_or_literal(&group.expr),
+        syn::Expr::Unary(unary) if matches!(unary.op, syn::UnOp::Deref(_)) => {
+            return is_place_or_literal(&unary.expr);
+        }
+        _ => return is_literal(expr),
+    }
+}
+
+impl Parse for Operand {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        fn parse_composite_varname(
+            input: &syn::parse::ParseStream,
+            vec: &mut Vec<String>,
+        ) -> syn::Result<()> {
+            loop {
+                if input.peek(syn::LitInt) {
+                    let lit = input.parse::<syn::LitInt>()?;
+                    vec.push(lit.to_string());
+                } else {
+                    let ident: syn::Ident = input.call(Ident::parse_any)?;
+                    vec.push(ident.to_string()); // parse_any also handles stuff like self
+                }
+
+                if input.peek(Token![.]) {
+                    // Throw the token away
+                    let _ = input.parse::<Token![.]>()?;
+                } else {
+                    // Field access is done ==> Finish the loop
+                    break;
+                }
+            }
+            return Ok(());
+        } // parse_composite_varname
+
+        // "val" followed by another identifier means that only the value of the
+        // operand should be displayed. Otherwise, "val" is treated as a
+        // variable name.
+        let display_value_only = if input.peek(syn::Ident)
+            && (input.peek2(syn::Ident) || input.peek2(Token![self]))
+            && input.fork().parse::<Ident>()? == "val"
+        {
+            input.parse::<Ident>()?;
+            true
+        } else {
+            false
+        };
+
+        let tokens = parse_operand_tokens(input)?;
+        let expr: syn::Expr = syn::parse2(tokens.clone())?;
+
+        // Resolve the alias, if the variable name should be displayed
+        let alias = if input.peek(Token![as]) {
+            input.parse::<Token![as]>()?;
+            let mut alias: Vec<String> = Vec::new();
+            parse_composite_varname(&input, &mut alias)?;
+            Some(alias.join("."))
+        } else {
+            None
+        };
+
+        let display_name = if display_value_only {
+            DisplayName::Hidden
+        } else if let Some(alias) = alias {
+            DisplayName::Alias(alias)
+        } else if is_literal(&expr) {
+            DisplayName::Hidden
+        } else {
+            DisplayName::Source
+        };
+
+        return Ok(Operand {
+            tokens,
+            display_name,
+        });
+    }
+}
+
+/// The error returned by `ensure_compare!` if the comparison is not true.
+enum EnsureError {
+    /// The comparison itself is converted into the error.
+    Comparison,
+    /// `, error`: The given expression is converted into the error.
+    Value(syn::Expr),
+    /// `=> function`: The given function is applied to the comparison.
+    Map(syn::Expr),
+}
+
+// Parser for the ensure_compare macro
+struct EnsureInfo {
+    comparison: ComparisonErrorInfo,
+    error: EnsureError,
+}
+
+impl EnsureInfo {
+    fn as_token_stream(&self) -> proc_macro2::TokenStream {
+        let comparison = self.comparison.as_token_stream();
+
+        let owned_comparison = if self.comparison.owned {
+            comparison.clone()
+        } else {
+            quote! {#comparison.map_err(compare_variables::Comparison::into_owned)}
+        };
+
+        let binding = mixed_site_ident("error");
+        match &self.error {
+            EnsureError::Comparison => quote! {
+                if let Err(#binding) = #owned_comparison {
+                    return Err(::core::convert::From::from(#binding));
+                }
+            },
+            EnsureError::Value(error) => quote! {
+                if #comparison.is_err() {
+                    return Err(::core::convert::From::from(#error));
+                }
+            },
+            EnsureError::Map(function) => quote! {
+                if let Err(#binding) = #owned_comparison.map_err(#function) {
+                    return Err(::core::convert::From::from(#binding));
+                }
+            },
+        }
+    }
+}
+
+impl Parse for EnsureInfo {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let comparison = ComparisonErrorInfo::parse(input)?;
+        let error = if input.peek(Token![=>]) {
+            input.parse::<Token![=>]>()?;
+            EnsureError::Map(input.parse()?)
+        } else if input.peek(Token![,]) {
+            input.parse::<Token![,]>()?;
+            EnsureError::Value(input.parse()?)
+        } else {
+            EnsureError::Comparison
+        };
+        let _ = input.parse::<Option<Token![,]>>()?;
+        return Ok(EnsureInfo { comparison, error });
+    }
+}
+
+// Parser for the validate macro: A comma-separated list of comparisons.
+struct ValidateInfo {
+    comparisons: Vec<ComparisonErrorInfo>,
+}
+
+impl ValidateInfo {
+    fn as_token_stream(&self) -> proc_macro2::TokenStream {
+        let set = mixed_site_ident("set");
+        let checks = self
+            .comparisons
+            .iter()
+            .map(|comparison| ValidateInfo::check_token_stream(comparison, &set));
+        quote! {
+            {
+                let mut #set = compare_variables::ComparisonSet::new();
+                #(#checks)*
+                #set.into_result()
+            }
+        }
+    }
+}
+
+impl ValidateInfo {
+    /// Returns the statement which records the comparison in the `ComparisonSet` `set`.
+    fn check_token_stream(comparison: &ComparisonErrorInfo, set: &Ident) -> TokenStream2 {
+        let tokens = comparison.as_token_stream();
+        let owned_comparison = if comparison.owned {
+            tokens
+        } else {
+            quote! {#tokens.map_err(compare_variables::Comparison::into_owned)}
+        };
+        quote! {
+            #set.check(#owned_comparison.map_err(compare_variables::AnyComparison::new));
+        }
+    }
+}
+
+impl Parse for ValidateInfo {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut comparisons = Vec::new();
+        while !input.is_empty() {
+            comparisons.push(ComparisonErrorInfo::parse(input)?);
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+        return Ok(ValidateInfo { comparisons });
+    }
+}
+
+/**
+A logical combination of comparisons and boolean operands. Like in Rust, `&&`
+binds more tightly than `||`.
+ */
+enum Logic {
+    Comparison(ComparisonErrorInfo),
+    /// An operand which is not compared, but must evaluate to a `bool`
+    Bool(Operand),
+    Not(Box<Logic>),
+    And(Vec<Logic>),
+    Or(Vec<Logic>),
+}
+
+impl Logic {
+    /// Returns the expression which creates the `compare_variables::Condition`.
+    fn as_token_stream(&self) -> proc_macro2::TokenStream {
+        return self.condition_token_stream(false);
+    }
+
+    /**
+    Returns the expression which creates the `compare_variables::Condition`.
+    Deferred operands are evaluated within a closure, therefore borrowed
+    temporary values (e.g. the result of a method call) would not outlive the
+    closure. Such comparisons are converted into owned comparisons.
+     */
+    fn condition_token_stream(&self, deferred: bool) -> proc_macro2::TokenStream {
+        match self {
+            Logic::Comparison(comparison) => {
+                let tokens = comparison.comparison_token_stream();
+                if deferred && comparison.borrows_temporary() {
+                    quote! {compare_variables::Condition::from(#tokens.into_owned())}
+                } else {
+                    quote! {compare_variables::Condition::from(#tokens)}
+                }
+            }
+            Logic::Bool(operand) => {
+                let tokens = operand.value_tokens();
+                let expression = match &operand.display_name {
+                    DisplayName::Alias(alias) => alias.clone(),
+                    _ => tokens_to_string(operand.tokens.clone()),
+                };
+                quote! {compare_variables::Condition::from_bool(#tokens, #expression)}
+            }
+            Logic::Not(operand) => {
+                let operand = operand.condition_token_stream(deferred);
+                quote! {::core::ops::Not::not(#operand)}
+            }
+            // The closures defer the evaluation of the following operands
+            Logic::And(operands) => {
+                let first = operands[0].condition_token_stream(deferred);
+                let others = operands[1..]
+                    .iter()
+                    .map(|operand| operand.condition_token_stream(true));
+                quote! {#first #(.and(|| #others))*}
+            }
+            Logic::Or(operands) => {
+                let first = operands[0].condition_token_stream(deferred);
+                let others = operands[1..]
+                    .iter()
+                    .map(|operand| operand.condition_token_stream(true));
+                quote! {#first #(.or(|| #others))*}
+            }
+        }
+    }
+
+    /// Returns true if the next tokens of `input` end an operand of `&&` or `||`.
+    fn peek_end(input: syn::parse::ParseStream) -> bool {
+        return input.is_empty() || input.peek(Token![,]) || peek_logical_operator(input);
+    }
+
+    fn parse_and(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut operands = vec![Logic::parse_unary(input)?];
+        while input.peek(Token![&&]) {
+            input.parse::<Token![&&]>()?;
+            operands.push(Logic::parse_unary(input)?);
+        }
+        if operands.len() == 1 {
+            return Ok(operands.remove(0));
+        }
+        return Ok(Logic::And(operands));
+    }
+
+    fn parse_unary(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // `!` negates a parenthesized group, if the group is a complete
+        // operand. Otherwise (e.g. `!(a) == b`), it is part of the operand.
+        if input.peek(Token![!]) && input.peek2(syn::token::Paren) {
+            let fork = input.fork();
+            fork.parse::<Token![!]>()?;
+            fork.parse::<TokenTree>()?;
+            if Logic::peek_end(&fork) {
+                input.parse::<Token![!]>()?;
+                return Ok(Logic::Not(Box::new(Logic::parse_group(input)?)));
+            }
+        }
+
+        // Parentheses which are a complete operand group a logical combination.
+        // Otherwise (e.g. `(x as f64) < y`), they are part of the operand.
+        if input.peek(syn::token::Paren) {
+            let fork = input.fork();
+            fork.parse::<TokenTree>()?;
+            if Logic::peek_end(&fork) {
+                return Logic::parse_group(input);
+            }
+        }
+
+        if ComparisonErrorInfo::peek(input)? {
+            return Ok(Logic::Comparison(ComparisonErrorInfo::parse(input)?));
+        }
+        return Ok(Logic::Bool(Operand::parse(input)?));
+    }
+
+    fn parse_group(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let content;
+        syn::parenthesized!(content in input);
+        let logic = Logic::parse(&content)?;
+        if !content.is_empty() {
+            return Err(content.error("expected `&&`, `||` or the end of the group"));
+        }
+        return Ok(logic);
+    }
+}
+
+impl Parse for Logic {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut operands = vec![Logic::parse_and(input)?];
+        while input.peek(Token![||]) {
+            input.parse::<Token![||]>()?;
+            operands.push(Logic::parse_and(input)?);
+        }
+        if operands.len() == 1 {
+            return Ok(operands.remove(0));
+        }
+        return Ok(Logic::Or(operands));
+    }
+}
+
+// Parser for the compare_variables, assert_compare and debug_assert_compare
+// macros: A comparison (or a logical combination of comparisons) followed by an
+// optional custom message.
+struct MessageInfo {
+    condition: Logic,
+    /// Custom message in the `format!` syntax
+    message: Option<TokenStream2>,
+}
+
+impl MessageInfo {
+    /**
+    Returns the expression which creates the comparison. The custom message is
+    only formatted if the comparison does not evaluate to true.
+     */
+    fn as_token_stream(&self) -> proc_macro2::TokenStream {
+        let comparison = match &self.condition {
+            Logic::Comparison(comparison) => comparison.as_token_stream(),
+            condition => {
+                if let Some(message) = self.message.as_ref() {
+                    return syn::Error::new_spanned(
+                        message,
+                        "custom messages are not supported for logical combinations of comparisons",
+                    )
+                    .to_compile_error();
+                }
+                let condition = condition.as_token_stream();
+                return quote! {#condition.into_result()};
+            }
+        };
+        match self.message.as_ref() {
+            Some(message) => {
+                let binding = mixed_site_ident("comparison");
+                quote! {
+                    #comparison.map_err(|#binding| #binding.with_message(::std::format!(#message)))
+                }
+            }
+            None => comparison,
+        }
+    }
+
+    /// Returns the assertion which panics if the comparison is not true.
+    fn as_assert_token_stream(&self) -> proc_macro2::TokenStream {
+        let binding = mixed_site_ident("comparison");
+        if let (Logic::Comparison(_), _) | (_, None) = (&self.condition, &self.message) {
+            let comparison = self.as_token_stream();
+            return quote! {
+                if let Err(#binding) = #comparison {
+                    panic!("{}", #binding)
+                }
+            };
+        }
+
+        // Logical combinations do not store the custom message, therefore
+        // it is appended to the panic message directly.
+        let condition = self.condition.as_token_stream();
+        let message = self.message.as_ref();
+        quote! {
+            if let Err(#binding) = #condition.into_result() {
+                panic!("{}: {}", #binding, ::core::format_args!(#message))
+            }
+        }
+    }
+}
+
+impl Parse for MessageInfo {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let span = input.span();
+        let condition = Logic::parse(input)?;
+        if let Logic::Bool(_) = condition {
+            return Err(syn::Error::new(
+                span,
+                "expected a comparison or a logical combination of comparisons",
+            ));
+        }
+        let message = if input.is_empty() {
+            None
+        } else {
+            input.parse::<Token![,]>()?;
+            let message: TokenStream2 = input.parse()?;
+            if message.is_empty() {
+                None
+            } else {
+                Some(message)
+            }
+        };
+        return Ok(MessageInfo { condition, message });
+    }
+}
+
+// Parser for the compare_variables macro
+struct ComparisonErrorInfo {
+    /// Whether the operands are moved into the comparison instead of being borrowed.
+    owned: bool,
+    first_arg: Operand,
+    links: Vec<(Operator, Operand)>,
+    tolerance: Option<Tolerance>,
+    range: Option<Range>,
+}
+
+impl ComparisonErrorInfo {
+    /**
+    Returns the expression which creates the comparison, wrapped in `Ok` if it
+    evaluates to true and in `Err` otherwise.
+     */
+    fn as_token_stream(&self) -> proc_macro2::TokenStream {
+        let comparison = self.comparison_token_stream();
+        return quote! {#comparison.into_result()};
+    }
+
+    /// Returns the expression which creates the comparison.
+    fn comparison_token_stream(&self) -> proc_macro2::TokenStream {
+        let owned = self.owned;
+        let first_arg = self.first_arg.as_token_stream(owned);
+
+        // Build the input for the compare_variables function
+        let stream = if let Some(range) = self.range.as_ref() {
+            match range {
+                Range::Bounds {
+                    start,
+                    end,
+                    inclusive,
+                } => {
+                    let start = Range::bound_token_stream(start.as_ref(), true, owned);
+                    let end = Range::bound_token_stream(end.as_ref(), *inclusive, owned);
+                    quote! {
+                        compare_variables::Comparison::new_in_bounds(#first_arg, #start, #end)
+                    }
+                }
+                Range::Value(range) => {
+                    let tokens = range.value_tokens();
+                    let name = range.name_token_stream();
+                    let stream = if owned {
+                        quote! {
+                            compare_variables::Comparison::new_in_range(#first_arg, &(#tokens), #name)
+                        }
+                    } else {
+                        quote! {
+                            compare_variables::Comparison::new_in_borrowed_range(#first_arg, &(#tokens), #name)
+                        }
+                    };
+                    match range.field_path_token_stream() {
+                        Some(path) => quote! {#stream.with_range_name(#path)},
+                        None => stream,
+                    }
+                }
+            }
+        } else if let Some(tolerance) = self.tolerance.as_ref() {
+            let tolerance = tolerance.as_token_stream();
+            let second_arg = self.links[0].1.as_token_stream(owned);
+            quote! {
+                compare_variables::Comparison::new_approx(#first_arg, #tolerance, #second_arg)
+            }
+        } else {
+            let links = self.links_as_token_stream();
+            quote! {
+                compare_variables::Comparison::new(#first_arg, #links)
+            }
+        };
+
+        // Attach the location of the macro invocation
+        return quote! {
+            #stream
+                .with_location({
+                    const LOCATION: compare_variables::SourceLocation = compare_variables::SourceLocation::new(
+                        ::core::file!(), ::core::line!(), ::core::column!(), ::core::module_path!()
+                    );
+                    &LOCATION
+                })
+        };
+    }
+
+    /// Returns all operands of the comparison.
+    fn operands_mut(&mut self) -> Vec<&mut Operand> {
+        let mut operands: Vec<&mut Operand> = vec![&mut self.first_arg];
+        operands.extend(self.links.iter_mut().map(|(_, operand)| operand));
+        match self.range.as_mut() {
+            Some(Range::Bounds { start, end, .. }) => {
+                operands.extend(start.iter_mut().chain(end.iter_mut()));
+            }
+            Some(Range::Value(range)) => operands.push(range),
+            None => (),
+        }
+        return operands;
+    }
+
+    /// Returns true if the comparison borrows a temporary value.
+    fn borrows_temporary(&self) -> bool {
+        if self.owned {
+            return false;
+        }
+        let mut operands: Vec<&Operand> = vec![&self.first_arg];
+        operands.extend(self.links.iter().map(|(_, operand)| operand));
+        match self.range.as_ref() {
+            Some(Range::Bounds { start, end, .. }) => {
+                operands.extend(start.iter().chain(end.iter()));
+            }
+            Some(Range::Value(range)) => operands.push(range),
+            None => (),
+        }
+        return operands.into_iter().any(Operand::is_temporary);
+    }
+
+    /**
+    Returns true if the next tokens of `input` are the keyword "owned" followed
+    by the first operand, which moves all operands into the comparison.
+    Otherwise, "owned" is treated as a variable name.
+     */
+    fn peek_owned(input: syn::parse::ParseStream) -> syn::Result<bool> {
+        return Ok(input.peek(syn::Ident)
+            && (input.peek2(syn::Ident)
+                || input.peek2(Token![self])
+                || input.peek2(syn::Lit)
+                || input.peek2(syn::token::Paren)
+                || input.peek2(syn::token::Brace))
+            && input.fork().parse::<Ident>()? == "owned");
+    }
+
+    /**
+    Returns true if the next tokens of `input` form a comparison (as opposed to
+    a single operand, which is a boolean operand of a logical combination).
+     */
+    fn peek(input: syn::parse::ParseStream) -> syn::Result<bool> {
+        let fork = input.fork();
+        if ComparisonErrorInfo::peek_owned(&fork)? {
+            fork.parse::<Ident>()?;
+        }
+        if Operand::parse(&fork).is_err() {
+            // Let the parser of the comparison report the error
+            return Ok(true);
+        }
+        return Ok(Operator::peek(&fork) || fork.peek(Token![in]));
+    }
+
+    /// Returns the links of the chain as an array of `(operator, value)` tuples.
+    fn links_as_token_stream(&self) -> proc_macro2::TokenStream {
+        let links = self.links.iter().map(|(operator, arg)| {
+            let operator = operator.as_token_stream();
+            let arg = arg.as_token_stream(self.owned);
+            quote! {(#operator, #arg)}
+        });
+        quote! {[#(#links),*]}
+    }
+}
+
+impl Parse for ComparisonErrorInfo {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        // Read the arguments. At least two arguments separated by an operator
+        // are required, afterwards the chain can be continued indefinitely.
+        let owned = if ComparisonErrorInfo::peek_owned(input)? {
+            input.parse::<Ident>()?;
+            true
+        } else {
+            false
+        };
+        let first_arg = Operand::parse(input)?;
+
+        // Range membership `x in range` is not combined with other operators
+        if input.peek(Token![in]) {
+            return Ok(ComparisonErrorInfo {
+                owned,
+                first_arg,
+                links: Vec::new(),
+                tolerance: None,
+                range: Some(Range::parse(input)?),
+            });
+        }
+
+        let mut links = vec![(Operator::parse(input)?, Operand::parse(input)?)];
+        while Operator::peek(input) {
+            links.push((Operator::parse(input)?, Operand::parse(input)?));
+        }
+
+        // The approximate equality operator requires a tolerance and can only
+        // be used to compare two values.
+        let is_approx = links
+            .iter()
+            .any(|(operator, _)| matches!(operator, Operator::ApproxEqual));
+        let tolerance = if Tolerance::peek(input) {
+            let span = input.span();
+            let tolerance = Tolerance::parse(input)?;
+            if !is_approx {
+                return Err(syn::Error::new(
+                    span,
+                    "a tolerance can only be given for the operator \"~=\"",
+                ));
+            }
+            Some(tolerance)
+        } else {
+            None
+        };
+        if is_approx {
+            if links.len() > 1 {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "the operator \"~=\" can only be used to compare two values",
+                ));
+            }
+            if tolerance.is_none() {
+                return Err(syn::Error::new(
+                    Span::call_site(),
+                    "the operator \"~=\" requires a tolerance, e.g. `x ~= 1.0, abs = 1e-9`",
+                ));
+            }
+        }
+
+        return Ok(ComparisonErrorInfo {
+            owned,
+            first_arg,
+            links,
+            tolerance,
+            range: None,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+
+    #[test]
+    fn test_parse_check_bounds_info() {
+        // Assert that the parse was successfull
+
+        // Float
+        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 < arg);
+        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 <= arg);
+        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 <= arg as alternative_arg);
+        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 < arg <= 1.0);
+        let _: ComparisonErrorInfo = syn::parse_quote!(0.0 < arg as alternative_arg <= 1.0);
+        let _: ComparisonErrorInfo = syn::parse_quote!(arg < 1.0);
         let _: ComparisonErrorInfo = syn::parse_quote!(arg <= 1.0);
         let _: ComparisonErrorInfo = syn::parse_quote!(arg as alternative_arg <= 1.0);
 
@@ -461,5 +3116,347 @@ mod tests {
         let _: ComparisonErrorInfo = syn::parse_quote!(-1 < arg);
         let _: ComparisonErrorInfo = syn::parse_quote!(-1 < -2);
         let _: ComparisonErrorInfo = syn::parse_quote!(-1 < arg as alternative_arg <= 2);
+
+        // Long chains
+        let info: ComparisonErrorInfo = syn::parse_quote!(a <= b <= c <= d <= e);
+        assert_eq!(info.links.len(), 4);
+        let info: ComparisonErrorInfo = syn::parse_quote!(0 < val a as x < b == 3 != 4);
+        assert_eq!(info.links.len(), 4);
+
+        // Expressions
+        let _: ComparisonErrorInfo = syn::parse_quote!(v.len() > 0);
+        let _: ComparisonErrorInfo = syn::parse_quote!(a[i] <= b);
+        let _: ComparisonErrorInfo = syn::parse_quote!(x + 1 < y);
+        let _: ComparisonErrorInfo = syn::parse_quote!((x as f64) < y as alternative_arg);
+        let _: ComparisonErrorInfo = syn::parse_quote!(x << 2 > Vec::<u8>::new().len());
+        let _: ComparisonErrorInfo = syn::parse_quote!(val f(x) <= { let y = 1; y });
+
+        // Approximate equality
+        let info: ComparisonErrorInfo = syn::parse_quote!(x ~= 1.0, abs = 1e-9);
+        assert!(matches!(info.tolerance, Some(Tolerance::Absolute(_))));
+        let info: ComparisonErrorInfo = syn::parse_quote!(x.y as z ~= f(1.0), rel = tol);
+        assert!(matches!(info.tolerance, Some(Tolerance::Relative(_))));
+        let info: ComparisonErrorInfo = syn::parse_quote!(1.0 ~= x, ulps = 4);
+        assert!(matches!(info.tolerance, Some(Tolerance::Ulps(_))));
+        assert!(syn::parse_str::<ComparisonErrorInfo>("x ~= 1.0").is_err());
+        assert!(syn::parse_str::<ComparisonErrorInfo>("x < y, abs = 1.0").is_err());
+        assert!(syn::parse_str::<ComparisonErrorInfo>("x ~= y ~= z, abs = 1.0").is_err());
+
+        // Range membership
+        let info: ComparisonErrorInfo = syn::parse_quote!(x in 0.0..=1.0);
+        assert!(matches!(
+            info.range,
+            Some(Range::Bounds {
+                start: Some(_),
+                end: Some(_),
+                inclusive: true
+            })
+        ));
+        let info: ComparisonErrorInfo = syn::parse_quote!(x.len() as len in ..f(y));
+        assert!(matches!(
+            info.range,
+            Some(Range::Bounds {
+                start: None,
+                end: Some(_),
+                inclusive: false
+            })
+        ));
+        let info: ComparisonErrorInfo = syn::parse_quote!(x in a.b..);
+        assert!(matches!(
+            info.range,
+            Some(Range::Bounds {
+                start: Some(_),
+                end: None,
+                ..
+            })
+        ));
+        let info: ComparisonErrorInfo = syn::parse_quote!(x in RANGE as valid);
+        assert!(matches!(info.range, Some(Range::Value(_))));
+
+        // Owned mode
+        let info: ComparisonErrorInfo = syn::parse_quote!(owned x < y);
+        assert!(info.owned);
+        let info: ComparisonErrorInfo = syn::parse_quote!(owned(x as f64) < 1.0);
+        assert!(info.owned);
+        let info: ComparisonErrorInfo = syn::parse_quote!(owned val x < y);
+        assert!(info.owned);
+        let info: ComparisonErrorInfo = syn::parse_quote!(owned < y);
+        assert!(!info.owned);
+        let info: ComparisonErrorInfo = syn::parse_quote!(owned.len() < y);
+        assert!(!info.owned);
+        let info: ComparisonErrorInfo = syn::parse_quote!(x < owned);
+        assert!(!info.owned);
+
+        // Assertions with custom messages
+        let info: MessageInfo = syn::parse_quote!(x < y);
+        assert!(info.message.is_none());
+        let info: MessageInfo = syn::parse_quote!(x < y,);
+        assert!(info.message.is_none());
+        let info: MessageInfo = syn::parse_quote!(x < y, "message {}", z);
+        assert!(info.message.is_some());
+        let info: MessageInfo = syn::parse_quote!(x ~= y, abs = 1e-3, "message");
+        assert!(info.message.is_some());
+        assert!(matches!(info.condition, Logic::Comparison(c) if c.tolerance.is_some()));
+        let info: MessageInfo = syn::parse_quote!(x in 0.., "message");
+        assert!(info.message.is_some());
+        assert!(matches!(info.condition, Logic::Comparison(c) if c.range.is_some()));
+
+        // Early return with custom errors
+        let info: EnsureInfo = syn::parse_quote!(x > 0);
+        assert!(matches!(info.error, EnsureError::Comparison));
+        let info: EnsureInfo = syn::parse_quote!(x > 0, MyError::Negative);
+        assert!(matches!(info.error, EnsureError::Value(_)));
+        let info: EnsureInfo = syn::parse_quote!(x > 0 => |c| MyError::Range(c));
+        assert!(matches!(info.error, EnsureError::Map(_)));
+        let info: EnsureInfo = syn::parse_quote!(x in 0.. => MyError::Range,);
+        assert!(matches!(info.error, EnsureError::Map(_)));
+        let info: EnsureInfo = syn::parse_quote!(x ~= 1.0, abs = 1e-3, MyError::NotEqual);
+        assert!(matches!(info.error, EnsureError::Value(_)));
+        assert!(syn::parse_str::<ComparisonErrorInfo>("x in 0..=").is_err());
+        assert!(syn::parse_str::<ComparisonErrorInfo>("x in 0..1 < y").is_err());
+
+        // Logical combinations
+        let info: MessageInfo = syn::parse_quote!(x > 0 || allow_zero);
+        let Logic::Or(operands) = info.condition else {
+            panic!()
+        };
+        assert!(matches!(
+            operands[..],
+            [Logic::Comparison(_), Logic::Bool(_)]
+        ));
+        let info: MessageInfo = syn::parse_quote!(a < b && c != d || !(e in 0..1) && f);
+        let Logic::Or(operands) = info.condition else {
+            panic!()
+        };
+        assert!(
+            matches!(&operands[..], [Logic::And(a), Logic::And(b)] if a.len() == 2 && b.len() == 2)
+        );
+        let info: MessageInfo =
+            syn::parse_quote!((a < b) && (x ~= 1.0, abs = 1e-3 || y), "message");
+        assert!(matches!(info.condition, Logic::And(_)) && info.message.is_some());
+        let info: MessageInfo =
+            syn::parse_quote!(x ~= 1.0, abs = 1e-3 || x in 0.. && owned v.len() > 0);
+        assert!(matches!(info.condition, Logic::Or(_)));
+        let info: MessageInfo = syn::parse_quote!(!x.is_empty() || (x as f64) < 2.0);
+        let Logic::Or(operands) = info.condition else {
+            panic!()
+        };
+        assert!(matches!(
+            operands[..],
+            [Logic::Bool(_), Logic::Comparison(_)]
+        ));
+        let info: MessageInfo = syn::parse_quote!(!(a) == b);
+        assert!(matches!(info.condition, Logic::Comparison(_)));
+        let info: MessageInfo = syn::parse_quote!((a < b));
+        assert!(matches!(info.condition, Logic::Comparison(_)));
+        let info: MessageInfo = syn::parse_quote!(&&x < f(|| 1));
+        assert!(matches!(info.condition, Logic::Comparison(_)));
+        assert!(syn::parse_str::<MessageInfo>("x").is_err());
+        assert!(syn::parse_str::<MessageInfo>("x > 0 ||").is_err());
+        assert!(syn::parse_str::<MessageInfo>("(x > 0 y) || z").is_err());
+
+        // Lists of comparisons
+        let info: ValidateInfo = syn::parse_quote!();
+        assert!(info.comparisons.is_empty());
+        let info: ValidateInfo = syn::parse_quote!(a > 0, b <= 10, c != d);
+        assert_eq!(info.comparisons.len(), 3);
+        let info: ValidateInfo = syn::parse_quote!(x ~= 1.0, abs = 1e-3, owned y in 0..,);
+        assert_eq!(info.comparisons.len(), 2);
+        assert!(info.comparisons[0].tolerance.is_some() && info.comparisons[1].owned);
+        assert!(syn::parse_str::<ValidateInfo>("a > 0 b > 0").is_err());
+    }
+
+    #[test]
+    fn test_resolve_fields() {
+        let path = Ident::new("path", Span::call_site());
+        let mut info: ComparisonErrorInfo =
+            syn::parse_quote!(_ >= self.min as lower <= self.limits.0 <= _.len() <= MAX);
+        for operand in info.operands_mut() {
+            operand.resolve_fields(&quote! {self.max}, &path);
+        }
+        let names: Vec<_> = info
+            .operands_mut()
+            .into_iter()
+            .map(|operand| match &operand.display_name {
+                DisplayName::Field { name, .. } => format!("field {name}"),
+                DisplayName::Alias(alias) => format!("alias {alias}"),
+                DisplayName::Source => tokens_to_string(operand.tokens.clone()),
+                DisplayName::Hidden => String::new(),
+            })
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "field max",
+                "alias lower",
+                "field limits.0",
+                "self.max.len()",
+                "MAX"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_const_compare_info() {
+        let info: ConstCompareInfo = syn::parse_quote!(0 < N as len <= MAX);
+        assert!(!info.is_unsuffixed_literal());
+        let names: Vec<_> = info
+            .operands()
+            .iter()
+            .map(|operand| operand.name())
+            .collect();
+        assert_eq!(
+            names,
+            [None, Some(String::from("len")), Some(String::from("MAX"))]
+        );
+
+        let info: ConstCompareInfo = syn::parse_quote!(-5 < (3) <= 1_000);
+        assert!(info.is_unsuffixed_literal());
+        let info: ConstCompareInfo = syn::parse_quote!(-5 < 3u8);
+        assert!(!info.is_unsuffixed_literal());
+
+        assert!(syn::parse2::<ConstCompareInfo>(quote!(x in 0..10)).is_err());
+        assert!(syn::parse_str::<ConstCompareInfo>("x ~= 1, abs = 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_bounded_info() {
+        let mut info: BoundedInfo = syn::parse_quote! {
+            #[derive(Copy)]
+            pub Percent: f64 where 0.0 <= _ <= 100.0, serde
+        };
+        assert_eq!(info.attrs.len(), 1);
+        assert_eq!(info.name, "Percent");
+        assert_eq!(info.source, "0.0 <= _ <= 100.0");
+        assert!(info.serde);
+        let names: Vec<_> = info
+            .comparison
+            .operands_mut()
+            .into_iter()
+            .map(|operand| match &operand.display_name {
+                DisplayName::Alias(alias) => alias.clone(),
+                _ => String::new(),
+            })
+            .collect();
+        assert_eq!(names, ["", "Percent", ""]);
+
+        let info: BoundedInfo = syn::parse_str("Level: u8 where _ ~= 5, abs = 5").unwrap();
+        assert!(!info.serde);
+        assert_eq!(info.source, "_ ~= 5, abs = 5");
+        assert!(info.comparison.tolerance.is_some());
+    }
+
+    #[test]
+    fn test_replace_self() {
+        let this = Ident::new("this", Span::call_site());
+        let mut info: ComparisonErrorInfo =
+            syn::parse_quote!(self.lo <= self.hi.max(self.lo) as upper <= val MAX);
+        for operand in info.operands_mut() {
+            operand.replace_self(&this);
+        }
+        let operands: Vec<_> = info
+            .operands_mut()
+            .into_iter()
+            .map(|operand| {
+                let name = match &operand.display_name {
+                    DisplayName::Alias(alias) => alias.clone(),
+                    DisplayName::Hidden => String::new(),
+                    _ => unreachable!(),
+                };
+                return (name, tokens_to_string(operand.tokens.clone()));
+            })
+            .collect();
+        assert_eq!(
+            operands,
+            [
+                (String::from("self.lo"), String::from("this.lo")),
+                (String::from("upper"), String::from("this.hi.max(this.lo)")),
+                (String::new(), String::from("MAX")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_contract_attribute_paths() {
+        let kind = |path: syn::Path| ContractKind::from_path(&path);
+        assert!(kind(syn::parse_quote!(requires)) == Some(ContractKind::Requires));
+        assert!(kind(syn::parse_quote!(compare_variables::ensures)) == Some(ContractKind::Ensures));
+        assert!(kind(syn::parse_quote!(::compare_variables::requires)).is_some());
+        assert!(kind(syn::parse_quote!(other_crate::requires)).is_none());
+        assert!(kind(syn::parse_quote!(compare_variables::other::ensures)).is_none());
+        assert!(kind(syn::parse_quote!(doc)).is_none());
+    }
+
+    #[test]
+    fn test_early_returns() {
+        let signature: syn::Signature = syn::parse_quote!(fn f(x: Option<u8>) -> Option<u8>);
+        let block: syn::Block = syn::parse_quote!({
+            let value = x?;
+            if value == 0 {
+                return None;
+            }
+            let closure = || {
+                return 1;
+            };
+            fn nested() -> u8 {
+                return 2;
+            }
+            return Some(value + closure() + nested());
+        });
+        let ret = Ident::new("ret", Span::call_site());
+        let tokens = bind_return_value(&signature, &block, &ret).to_string();
+        assert_eq!(tokens.matches("break 'body").count(), 2);
+        assert_eq!(tokens.matches("return").count(), 2);
+        assert!(tokens.contains("Some (value + closure () + nested ()) }"));
+    }
+
+    #[test]
+    fn test_parse_contract_info() {
+        let mut contract = ContractInfo::parse(
+            ContractKind::Requires,
+            quote! {0.0 <= p <= 1.0, mode = result},
+        )
+        .unwrap();
+        assert!(contract.mode == ContractMode::Result);
+        assert_eq!(contract.comparison.operands_mut().len(), 3);
+        assert_eq!(
+            contract.doc(),
+            " - Requires `0.0 <= p <= 1.0` (returns an error otherwise)."
+        );
+
+        let contract = ContractInfo::parse(
+            ContractKind::Ensures,
+            syn::parse_str("ret ~= x, abs = 0.1").unwrap(),
+        )
+        .unwrap();
+        assert!(contract.mode == ContractMode::Panic);
+        assert_eq!(
+            contract.doc(),
+            " - Ensures `ret ~= x, abs = 0.1` (panics otherwise)."
+        );
+
+        assert!(ContractInfo::parse(ContractKind::Requires, quote! {x > 0, mode = never}).is_err());
+    }
+
+    #[test]
+    fn test_tokens_to_string() {
+        assert_eq!(tokens_to_string(quote!(self.field.0)), "self.field.0");
+        assert_eq!(tokens_to_string(quote!(v.len())), "v.len()");
+        assert_eq!(tokens_to_string(quote!(a[i] + -1)), "a[i] + -1");
+        assert_eq!(tokens_to_string(quote!(x << 2)), "x << 2");
+        assert_eq!(
+            tokens_to_string(quote!(v.iter().sum::<i32>())),
+            "v.iter().sum::<i32>()"
+        );
+        assert_eq!(
+            tokens_to_string(quote!(Vec::<Vec<u8>>::new())),
+            "Vec::<Vec<u8>>::new()"
+        );
+        assert_eq!(tokens_to_string(quote!((x as f64))), "(x as f64)");
+        assert_eq!(tokens_to_string(quote!(f(&x, *y))), "f(&x, *y)");
+        assert_eq!(
+            tokens_to_string(quote!(vec![1, 2].len())),
+            "vec![1, 2].len()"
+        );
     }
 }
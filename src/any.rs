@@ -0,0 +1,134 @@
+/*!
+A type-erased comparison error which can be created from any comparison via
+the `?` operator, see [`AnyComparison`].
+ */
+
+use std::fmt::Debug;
+
+use crate::{Comparison, ComparisonErrorTrait, ComparisonReport};
+
+/**
+A boxed [`ComparisonErrorTrait`] object which can be used as the single error
+type of a function performing comparisons of different types.
+
+Every [`Comparison`] is converted into an [`AnyComparison`] via [`From`] and
+therefore via the `?` operator, including comparisons of borrowed values as
+created by [`compare_variables`](crate::compare_variables) by default. The
+conversion freezes the comparison into a [`ComparisonReport`] (see
+[`Comparison::to_report`]), since the compared values may borrow local
+variables. A comparison of owned values which should keep its concrete type
+for downcasting is wrapped via [`AnyComparison::new`] instead.
+
+The [`ComparisonErrorTrait`] object is accessible via [`Deref`](std::ops::Deref),
+which includes the downcasting functions.
+
+# Examples
+```
+use compare_variables::{compare_variables, AnyComparison, Comparison, ComparisonReport};
+
+fn check(count: i32, ratio: f64, name: &str) -> Result<(), AnyComparison> {
+    compare_variables!(count > 0)?;
+    compare_variables!(0.0 <= ratio <= 1.0)?;
+    compare_variables!(name.len() <= 8)?;
+    return Ok(());
+}
+
+assert!(check(1, 0.5, "a").is_ok());
+
+let err = check(0, 0.5, "a").unwrap_err();
+assert_eq!(err.to_string(), "`count (value: 0) > 0` is false");
+assert!(err.is::<ComparisonReport>());
+
+let err = check(1, 1.5, "a").unwrap_err();
+assert_eq!(err.variable_name(1), Some("ratio"));
+assert_eq!(err.value_debug(1).as_deref(), Some("1.5"));
+
+let err = check(1, 0.5, "too long a name").unwrap_err();
+assert_eq!(err.len(), 2);
+
+// Owned comparisons keep their type via `AnyComparison::new`
+let count = 0;
+let err = AnyComparison::new(compare_variables!(owned count > 0).unwrap_err());
+assert!(err.is::<Comparison<i32>>());
+```
+ */
+pub struct AnyComparison(Box<dyn ComparisonErrorTrait>);
+
+impl AnyComparison {
+    /**
+    Returns a new instance of [`AnyComparison`] wrapping the given comparison.
+
+    ```
+    use compare_variables::{compare_variables, AnyComparison, Comparison};
+
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    struct Version(u32, u32);
+
+    let version = Version(1, 2);
+    let err = AnyComparison::new(
+        compare_variables!(owned version >= Version(2, 0)).unwrap_err()
+    );
+    assert!(err.is::<Comparison<Version>>());
+    ```
+     */
+    pub fn new<T: ComparisonErrorTrait>(comparison: T) -> Self {
+        return Self(Box::new(comparison));
+    }
+
+    /**
+    Returns the underlying [`ComparisonErrorTrait`] object.
+     */
+    pub fn into_inner(self) -> Box<dyn ComparisonErrorTrait> {
+        return self.0;
+    }
+}
+
+impl std::ops::Deref for AnyComparison {
+    type Target = dyn ComparisonErrorTrait;
+
+    fn deref(&self) -> &Self::Target {
+        return self.0.as_ref();
+    }
+}
+
+impl std::ops::DerefMut for AnyComparison {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        return self.0.as_mut();
+    }
+}
+
+impl std::fmt::Display for AnyComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return std::fmt::Display::fmt(&self.0, f);
+    }
+}
+
+impl Debug for AnyComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return Debug::fmt(&self.0, f);
+    }
+}
+
+impl std::error::Error for AnyComparison {}
+
+/**
+The comparison is stored as a [`ComparisonReport`], since its values may borrow
+local variables or may not be [`Send`] or [`Sync`].
+ */
+impl<L: PartialOrd<R> + Debug, R: Debug> From<Comparison<L, R>> for AnyComparison {
+    fn from(value: Comparison<L, R>) -> Self {
+        return Self::new::<ComparisonReport>(value.to_report());
+    }
+}
+
+impl From<Box<dyn ComparisonErrorTrait>> for AnyComparison {
+    fn from(value: Box<dyn ComparisonErrorTrait>) -> Self {
+        return Self(value);
+    }
+}
+
+impl From<AnyComparison> for Box<dyn ComparisonErrorTrait> {
+    fn from(value: AnyComparison) -> Self {
+        return value.0;
+    }
+}
@@ -0,0 +1,176 @@
+/*!
+Tolerance-aware equality for floating point values, see [`ApproxEq`] and
+[`Tolerance`].
+ */
+
+use crate::ComparisonOutcome;
+
+/**
+The allowed deviation of two values which are compared approximately via
+[`Comparison::new_approx`](crate::Comparison::new_approx).
+
+Each variant defines how the deviation between the two values is measured and
+contains the largest deviation for which the values are still considered equal.
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Tolerance {
+    /// The absolute difference `|a - b|` of the two values.
+    Absolute(f64),
+    /// The absolute difference of the two values divided by the larger
+    /// magnitude of the two values: `|a - b| / max(|a|, |b|)`.
+    Relative(f64),
+    /// The number of representable floating point values between the two
+    /// values ("units in the last place").
+    Ulps(u64),
+}
+
+impl Tolerance {
+    /**
+    Returns the largest deviation for which two values are considered equal.
+     */
+    pub fn limit(&self) -> f64 {
+        match self {
+            Tolerance::Absolute(limit) => return *limit,
+            Tolerance::Relative(limit) => return *limit,
+            Tolerance::Ulps(limit) => return *limit as f64,
+        }
+    }
+
+    /**
+    Returns whether the given `deviation` (as calculated by
+    [`ApproxEq::deviation`]) is within the tolerance. If `deviation` is
+    [`None`], [`ComparisonOutcome::Incomparable`] is returned.
+
+    ```
+    use compare_variables::{ComparisonOutcome, Tolerance};
+
+    assert_eq!(Tolerance::Absolute(0.1).outcome(Some(0.05)), ComparisonOutcome::True);
+    assert_eq!(Tolerance::Ulps(4).outcome(Some(5.0)), ComparisonOutcome::False);
+    assert_eq!(Tolerance::Relative(0.1).outcome(None), ComparisonOutcome::Incomparable);
+    ```
+     */
+    pub fn outcome(&self, deviation: Option<f64>) -> ComparisonOutcome {
+        match deviation {
+            Some(deviation) if deviation <= self.limit() => return ComparisonOutcome::True,
+            Some(_) => return ComparisonOutcome::False,
+            None => return ComparisonOutcome::Incomparable,
+        }
+    }
+
+    /**
+    Writes a description of the `deviation` and the tolerance, which is used
+    in the message of a [`Comparison`](crate::Comparison).
+     */
+    pub(crate) fn fmt_deviation(
+        &self,
+        deviation: f64,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Tolerance::Absolute(limit) => {
+                write!(f, "absolute deviation: {deviation:?}, tolerance: {limit:?}")
+            }
+            Tolerance::Relative(limit) => {
+                write!(f, "relative deviation: {deviation:?}, tolerance: {limit:?}")
+            }
+            Tolerance::Ulps(limit) => {
+                write!(f, "deviation: {deviation} ULPs, tolerance: {limit} ULPs")
+            }
+        }
+    }
+}
+
+/**
+Calculates the deviation between two values for approximate comparisons (`~=`,
+see [`Comparison::new_approx`](crate::Comparison::new_approx)).
+
+This trait is implemented for `f32` and `f64` and, if the feature flag `uom` is
+enabled, for all [uom](https://crates.io/crates/uom) quantities whose
+underlying storage type implements this trait. For quantities, the absolute
+tolerance is given in the base units of the quantity (e.g. meter for a length).
+
+# Examples
+```
+use compare_variables::{ApproxEq, Tolerance};
+
+assert_eq!(1.5f64.deviation(&1.0, Tolerance::Absolute(0.0)), Some(0.5));
+assert_eq!(1.5f64.deviation(&1.0, Tolerance::Relative(0.0)), Some(0.5 / 1.5));
+assert_eq!(1.0f64.deviation(&(1.0 + f64::EPSILON), Tolerance::Ulps(0)), Some(1.0));
+assert_eq!(f64::NAN.deviation(&1.0, Tolerance::Absolute(0.0)), None);
+```
+ */
+pub trait ApproxEq<Rhs: ?Sized = Self> {
+    /**
+    Returns the deviation between `self` and `other`, measured as specified by
+    `tolerance` (the limit contained in `tolerance` is not relevant here). If
+    the two values cannot be compared (e.g. because one of them is NaN),
+    [`None`] is returned.
+     */
+    fn deviation(&self, other: &Rhs, tolerance: Tolerance) -> Option<f64>;
+}
+
+macro_rules! impl_approx_eq_float {
+    ($float:ty, $int:ty) => {
+        impl ApproxEq for $float {
+            fn deviation(&self, other: &Self, tolerance: Tolerance) -> Option<f64> {
+                if self.is_nan() || other.is_nan() {
+                    return None;
+                }
+
+                // Covers equal infinities as well as +0.0 and -0.0
+                if self == other {
+                    return Some(0.0);
+                }
+
+                let first = *self as f64;
+                let second = *other as f64;
+                match tolerance {
+                    Tolerance::Absolute(_) => return Some((first - second).abs()),
+                    Tolerance::Relative(_) => {
+                        let largest = first.abs().max(second.abs());
+                        if largest.is_infinite() {
+                            return Some(f64::INFINITY);
+                        }
+                        return Some((first - second).abs() / largest);
+                    }
+                    Tolerance::Ulps(_) => {
+                        // Map the bit representation onto an integer scale
+                        // which is monotonic in the floating point value.
+                        fn ordered(value: $float) -> i128 {
+                            let bits = value.to_bits() as $int;
+                            if bits < 0 {
+                                return (<$int>::MIN as i128) - (bits as i128);
+                            } else {
+                                return bits as i128;
+                            }
+                        }
+                        return Some((ordered(*self) - ordered(*other)).unsigned_abs() as f64);
+                    }
+                }
+            }
+        }
+    };
+}
+
+impl_approx_eq_float!(f32, i32);
+impl_approx_eq_float!(f64, i64);
+
+impl<A: ApproxEq<B> + ?Sized, B: ?Sized> ApproxEq<&B> for &A {
+    fn deviation(&self, other: &&B, tolerance: Tolerance) -> Option<f64> {
+        return (**self).deviation(*other, tolerance);
+    }
+}
+
+#[cfg(feature = "uom")]
+impl<D, U, V> ApproxEq for uom::si::Quantity<D, U, V>
+where
+    D: uom::si::Dimension + ?Sized,
+    U: uom::si::Units<V> + ?Sized,
+    V: uom::num::Num + uom::Conversion<V> + ApproxEq,
+{
+    fn deviation(&self, other: &Self, tolerance: Tolerance) -> Option<f64> {
+        return self.value.deviation(&other.value, tolerance);
+    }
+}
@@ -0,0 +1,197 @@
+/*!
+Comparisons of primitive values in const contexts, see [`ConstValue`] and
+[`ConstMessage`].
+ */
+
+use std::cmp::Ordering;
+
+/**
+A primitive value which can be compared and formatted in const contexts.
+
+Trait methods such as [`PartialOrd::partial_cmp`] and [`Debug::fmt`](std::fmt::Debug::fmt)
+cannot be called in const contexts. This wrapper therefore provides inherent
+const methods for the primitive integer types and [`bool`]. Together with
+[`ComparisonOperator::is_true_for_ordering`](crate::ComparisonOperator::is_true_for_ordering),
+it is the const counterpart to [`ComparisonOperator::is_true`](crate::ComparisonOperator::is_true).
+The macros `const_compare!` and `static_assert_compare!` are built on it.
+
+# Examples
+```
+use compare_variables::{ConstMessage, ConstValue};
+use std::cmp::Ordering;
+
+const ORDERING: Ordering = ConstValue(3u8).ordering(4);
+assert_eq!(ORDERING, Ordering::Less);
+
+const MESSAGE: ConstMessage<8> = {
+    let mut message = ConstMessage::new();
+    ConstValue(-42i32).write(&mut message);
+    message
+};
+assert_eq!(MESSAGE.as_str(), "-42");
+```
+ */
+#[derive(Debug, Clone, Copy)]
+pub struct ConstValue<T>(pub T);
+
+macro_rules! impl_const_value {
+    ($push:ident: $($t:ty),*) => {
+        $(
+            impl ConstValue<$t> {
+                /**
+                Returns the ordering of the wrapped value with respect to `other`.
+                 */
+                pub const fn ordering(self, other: $t) -> Ordering {
+                    if self.0 < other {
+                        return Ordering::Less;
+                    } else if self.0 > other {
+                        return Ordering::Greater;
+                    } else {
+                        return Ordering::Equal;
+                    }
+                }
+
+                /**
+                Appends the wrapped value to `message`, formatted like [`Debug`](std::fmt::Debug).
+                 */
+                pub const fn write<const N: usize>(self, message: &mut ConstMessage<N>) {
+                    message.$push(self.0 as _);
+                }
+            }
+        )*
+    };
+}
+
+impl_const_value!(push_signed: i8, i16, i32, i64, i128, isize);
+impl_const_value!(push_unsigned: u8, u16, u32, u64, u128, usize);
+
+impl ConstValue<bool> {
+    /**
+    Returns the ordering of the wrapped value with respect to `other`, where
+    `false` is less than `true`.
+     */
+    pub const fn ordering(self, other: bool) -> Ordering {
+        return ConstValue(self.0 as u8).ordering(other as u8);
+    }
+
+    /**
+    Appends the wrapped value to `message`, formatted like [`Debug`](std::fmt::Debug).
+     */
+    pub const fn write<const N: usize>(self, message: &mut ConstMessage<N>) {
+        if self.0 {
+            message.push_str("true");
+        } else {
+            message.push_str("false");
+        }
+    }
+}
+
+/**
+A string with a fixed capacity of `N` bytes which can be built in const
+contexts.
+
+`const_compare!` returns the message of a failed comparison as a
+[`ConstMessage`], whose capacity is chosen by the macro so the message always
+fits. Text exceeding the capacity is truncated.
+ */
+#[derive(Clone, Copy)]
+pub struct ConstMessage<const N: usize> {
+    bytes: [u8; N],
+    len: usize,
+}
+
+impl<const N: usize> ConstMessage<N> {
+    /**
+    Returns an empty message.
+     */
+    pub const fn new() -> Self {
+        return Self {
+            bytes: [0; N],
+            len: 0,
+        };
+    }
+
+    /**
+    Appends `text` to the message.
+     */
+    pub const fn push_str(&mut self, text: &str) {
+        let bytes = text.as_bytes();
+        let mut index = 0;
+        while index < bytes.len() && self.len < N {
+            self.bytes[self.len] = bytes[index];
+            self.len += 1;
+            index += 1;
+        }
+    }
+
+    /**
+    Appends the decimal representation of `value` to the message.
+     */
+    pub const fn push_unsigned(&mut self, value: u128) {
+        // u128::MAX has 39 digits
+        let mut digits = [0u8; 39];
+        let mut start = digits.len();
+        let mut remainder = value;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (remainder % 10) as u8;
+            remainder /= 10;
+            if remainder == 0 {
+                break;
+            }
+        }
+        while start < digits.len() && self.len < N {
+            self.bytes[self.len] = digits[start];
+            self.len += 1;
+            start += 1;
+        }
+    }
+
+    /**
+    Appends the decimal representation of `value` to the message.
+     */
+    pub const fn push_signed(&mut self, value: i128) {
+        if value < 0 {
+            self.push_str("-");
+        }
+        self.push_unsigned(value.unsigned_abs());
+    }
+
+    /**
+    Returns the message as a string slice.
+     */
+    pub const fn as_str(&self) -> &str {
+        let (bytes, _) = self.bytes.split_at(self.len);
+        match std::str::from_utf8(bytes) {
+            Ok(text) => return text,
+            // Truncation split a multi-byte character
+            Err(error) => {
+                let (valid, _) = bytes.split_at(error.valid_up_to());
+                match std::str::from_utf8(valid) {
+                    Ok(text) => return text,
+                    Err(_) => return "",
+                }
+            }
+        }
+    }
+}
+
+impl<const N: usize> Default for ConstMessage<N> {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
+impl<const N: usize> std::fmt::Display for ConstMessage<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(self.as_str());
+    }
+}
+
+impl<const N: usize> std::fmt::Debug for ConstMessage<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return std::fmt::Debug::fmt(self.as_str(), f);
+    }
+}
+
+impl<const N: usize> std::error::Error for ConstMessage<N> {}
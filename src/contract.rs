@@ -0,0 +1,48 @@
+/*!
+Support for the code generated by the attributes `ensures` and `invariant`.
+
+The attributes evaluate the body of a function as a labeled block, so the
+return value can be checked before it is returned. The `?` operator within the
+body is rewritten into a `match` on [`Try::branch`] which breaks out of the
+block, as the unstable `std::ops::Try` trait cannot be used directly.
+ */
+
+/**
+The result of [`Try::branch`]: Either the value to continue with or the value
+the function returns early.
+ */
+pub enum Branch<C, B> {
+    Continue(C),
+    Break(B),
+}
+
+/**
+The operand of the `?` operator within a function returning `R`.
+ */
+pub trait Try<R> {
+    type Output;
+
+    fn branch(self) -> Branch<Self::Output, R>;
+}
+
+impl<T, E, U, F: From<E>> Try<Result<U, F>> for Result<T, E> {
+    type Output = T;
+
+    fn branch(self) -> Branch<T, Result<U, F>> {
+        match self {
+            Ok(value) => return Branch::Continue(value),
+            Err(error) => return Branch::Break(Err(From::from(error))),
+        }
+    }
+}
+
+impl<T, U> Try<Option<U>> for Option<T> {
+    type Output = T;
+
+    fn branch(self) -> Branch<T, Option<U>> {
+        match self {
+            Some(value) => return Branch::Continue(value),
+            None => return Branch::Break(None),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 [`Comparison`]: crate::Comparison
 [`PartialOrd`]: std::cmp::PartialOrd
 [`compare_variables`]: crate::compare_variables
+[`ApproxEq`]: crate::ApproxEq
+[`std::ops::RangeBounds`]: std::ops::RangeBounds
 
 A library for comparing the ordering of variables and producing useful messages.
 
@@ -9,21 +11,81 @@ A library for comparing the ordering of variables and producing useful messages.
 #![doc = include_str!("../docs/main.md")]
 
 #[cfg(feature = "proc_macro")]
-pub use compare_variables_macro::compare_variables;
+pub use compare_variables_macro::{
+    Validate, assert_compare, bounded, compare_variables, const_compare, debug_assert_compare,
+    ensure_compare, ensures, invariant, requires, static_assert_compare, validate,
+};
+
+// Used by the code generated by `#[ensures]` and `#[invariant]`
+#[doc(hidden)]
+pub mod contract;
+
+// Used by the code generated by `bounded!`
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde;
+
+mod approx;
+pub use approx::{ApproxEq, Tolerance};
+
+mod location;
+pub use location::SourceLocation;
+
+mod style;
+pub use style::{
+    ComparisonView, ExpressionStyle, LocalizedStyle, MessageStyle, NaturalLanguageStyle,
+    default_style, set_default_style,
+};
+
+#[cfg(feature = "serde")]
+mod serialization;
+
+mod report;
+pub use report::{ComparisonReport, RenderedValue};
+
+mod any;
+pub use any::AnyComparison;
+
+mod set;
+pub use set::ComparisonSet;
+
+mod logic;
+pub use logic::{Condition, DynComparison, Junction};
+
+mod validate;
+pub use validate::{Validate, field_path};
+
+mod constant;
+pub use constant::{ConstMessage, ConstValue};
+
+mod locale;
+pub use locale::{
+    CATALOG_KEYS, Catalog, CatalogError, English, German, MessageCatalog, bundled_catalog,
+};
+
+use std::borrow::Cow;
+use std::ops::{Bound, RangeBounds};
 
 // ===============================================================================================
 
 /**
-Compare the [partial ordering](PartialOrd) of two or three variables and format
+Compare the [partial ordering](PartialOrd) of a chain of variables and format
 the result into a message.
 
-The constructor [`Comparison::new`] compares two to three input values with each
-other using the given [`ComparisonOperator`]s and returns an instance of this
-struct. The method [`Comparison::is_true`] can then be used to verify whether
-the comparison evaluates to true or not. For seamless operation with the `?`
+The constructor [`Comparison::new`] compares a chain of input values (e.g.
+`a <= b < c <= d`) using the given [`ComparisonOperator`]s and returns an
+instance of this struct. Each value is compared to its successor in the chain.
+The method [`Comparison::is_true`] can then be used to verify whether the
+comparison evaluates to true or not, while [`Comparison::failed_links`] reports
+which parts of a chained comparison are violated. For seamless operation with the `?`
 operator, [`Comparison::new_checked`] creates a [`Comparison`] which is wrapped
 in [`Ok`] if [`Comparison::is_true`] and in [`Err`] otherwise.
 
+The values of the chain do not need to have the same type: The first value has
+the type `L` and all other values have the type `R` (which defaults to `L`), so
+that e.g. a wrapper type can be compared with `f64` if it implements
+`PartialOrd<f64>`.
+
 # Examples
 ```
 use compare_variables::{Comparison, ComparisonValue, ComparisonOperator};
@@ -31,10 +93,7 @@ use compare_variables::{Comparison, ComparisonValue, ComparisonOperator};
 fn my_checked_sub(first: usize, second: usize) -> Result<usize, Comparison<usize>> {
     Comparison::new_checked(
         ComparisonValue::new(first, None),
-        ComparisonOperator::Greater,
-        ComparisonValue::new(second, None),
-        ComparisonOperator::Equal,
-        None,
+        [(ComparisonOperator::Greater, ComparisonValue::new(second, None))],
     )?;
     return Ok(first - second);
 }
@@ -53,37 +112,51 @@ use compare_variables::{Comparison, ComparisonValue, ComparisonOperator};
 
 let cmp = Comparison::new(
     ComparisonValue::new(1, Some("x")),
-    ComparisonOperator::Greater,
-    ComparisonValue::new(2, None),
-    ComparisonOperator::Equal,
-    None,
+    [(ComparisonOperator::Greater, ComparisonValue::new(2, None))],
 );
 assert_eq!(cmp.to_string(), "`x (value: 1) > 2` is false");
 
 let cmp = Comparison::new(
     ComparisonValue::new(1, Some("x")),
-    ComparisonOperator::Greater,
-    ComparisonValue::new(2, Some("y")),
-    ComparisonOperator::Equal,
-    None,
+    [(ComparisonOperator::Greater, ComparisonValue::new(2, Some("y")))],
 );
 assert_eq!(cmp.to_string(), "`x (value: 1) > y (value: 2)` is false");
 
 let cmp = Comparison::new(
     ComparisonValue::new(1, Some("x")),
-    ComparisonOperator::Inequal,
-    ComparisonValue::new(1, Some("x")),
-    ComparisonOperator::Equal,
-    None,
+    [(ComparisonOperator::Inequal, ComparisonValue::new(1, Some("x")))],
 );
 assert_eq!(cmp.to_string(), "`x (value: 1) != x (value: 1)` is false");
 ```
 
+## Chained comparisons
+
+Any number of values can be chained together:
+```
+use compare_variables::{Comparison, ComparisonValue, ComparisonOperator};
+
+let cmp = Comparison::new(
+    ComparisonValue::new(0, Some("min")),
+    [
+        (ComparisonOperator::LesserOrEqual, ComparisonValue::new(1, Some("low"))),
+        (ComparisonOperator::LesserOrEqual, ComparisonValue::new(5, Some("nominal"))),
+        (ComparisonOperator::LesserOrEqual, ComparisonValue::new(4, Some("high"))),
+    ],
+);
+assert_eq!(cmp.len(), 4);
+assert_eq!(cmp.failed_links().collect::<Vec<_>>(), vec![2]);
+assert_eq!(
+    cmp.to_string(),
+    "`min (value: 0) <= low (value: 1) <= nominal (value: 5) <= high (value: 4)` is false \
+    (failed: `nominal (value: 5) <= high (value: 4)`)"
+);
+```
+
 ## Construction via macro
 
 It is recommended to use the procedural macro [`compare_variables`] to construct
 this struct (available via the feature flag  `proc_macro ` which is enabled by
-default). With the macro, the previous example simplifies to:
+default). With the macro, the previous examples simplify to:
 ```
 use compare_variables::compare_variables;
 
@@ -95,22 +168,69 @@ assert_eq!(msg.to_string(), "`x (value: 1) > 2` is false");
 
 let msg = compare_variables!(x > y).unwrap_or_else(|x| x);
 assert_eq!(msg.to_string(), "`x (value: 1) > y (value: 2)` is false");
+
+let (min, low, nominal, high) = (0, 1, 5, 4);
+let msg = compare_variables!(min <= low <= nominal <= high).unwrap_or_else(|x| x);
+assert_eq!(
+    msg.to_string(),
+    "`min (value: 0) <= low (value: 1) <= nominal (value: 5) <= high (value: 4)` is false \
+    (failed: `nominal (value: 5) <= high (value: 4)`)"
+);
 ```
 For more examples, consult the macro documentation.
  */
 #[derive(Clone)]
-pub struct Comparison<T: PartialOrd> {
-    first_val: ComparisonValue<T>,
-    comp_first_to_second: ComparisonOperator,
-    second_val: ComparisonValue<T>,
-    comp_second_to_third: ComparisonOperator,
-    third_val: Option<ComparisonValue<T>>,
-    is_true: bool,
+pub struct Comparison<L: PartialOrd<R>, R = L> {
+    first_val: ComparisonValue<L>,
+    /// All values of the chain except for the first one. Boxed slices are
+    /// used instead of vectors to keep `Result<_, Comparison>` small.
+    other_vals: Box<[ComparisonValue<R>]>,
+    operators: Box<[ComparisonOperator]>,
+    link_results: Box<[LinkResult]>,
+    /// Boxed for the same reason, since few comparisons are range memberships.
+    range: Option<Box<RangeMembership>>,
+    location: Option<&'static SourceLocation>,
+    message: Option<Box<str>>,
+}
+
+/// Result of the comparison between two neighbouring values of a chain.
+#[derive(Debug, Clone, Copy)]
+struct LinkResult {
+    ordering: Option<std::cmp::Ordering>,
+    outcome: ComparisonOutcome,
+    /// Deviation of the two values, if they have been compared approximately.
+    deviation: Option<f64>,
+    /// Tolerance of an approximate comparison. The operator of such a link is
+    /// always [`ComparisonOperator::Equal`].
+    tolerance: Option<Tolerance>,
 }
 
-impl<T: PartialOrd> Comparison<T> {
+/// Marks a comparison which checks whether a value lies within a range.
+#[derive(Debug, Clone)]
+struct RangeMembership {
+    /// Whether the range has a start bound. If so, the start bound is the
+    /// first value of the chain, followed by the checked value.
+    has_start: bool,
+    /// Name of the range, if it was given as a single value (e.g. a constant).
+    name: Option<Cow<'static, str>>,
+}
+
+impl RangeMembership {
+    /// Index of the checked value within the chain.
+    fn element(&self) -> usize {
+        return usize::from(self.has_start);
+    }
+}
+
+impl<L: PartialOrd<R>, R> Comparison<L, R> {
     /**
-    Returns a new [`Comparison`] by comparing two or three values.
+    Returns a new [`Comparison`] by comparing a chain of values.
+
+    The chain starts with `first_val`. Each entry of `links` contains an
+    operator and the value which is compared to the preceding value in the chain
+    using this operator. For example, `a < b <= c` is represented by the first
+    value `a` and the links `[(<, b), (<=, c)]`. If `links` is empty, the
+    comparison is trivially true.
 
     # Examples
     ```
@@ -118,47 +238,60 @@ impl<T: PartialOrd> Comparison<T> {
 
     let cmp = Comparison::new(
         ComparisonValue::new(1, None),
-        ComparisonOperator::Lesser,
-        ComparisonValue::new(2, None),
-        ComparisonOperator::Equal,
-        None,
+        [(ComparisonOperator::Lesser, ComparisonValue::new(2, None))],
     );
     assert_eq!(cmp.to_string(), "`1 < 2` is true");
 
     let cmp = Comparison::new(
         ComparisonValue::new(1, Some("x")),
-        ComparisonOperator::Greater,
-        ComparisonValue::new(2, None),
-        ComparisonOperator::Equal,
-        None,
+        [(ComparisonOperator::Greater, ComparisonValue::new(2, None))],
     );
     assert_eq!(cmp.to_string(), "`x (value: 1) > 2` is false");
+
+    let cmp = Comparison::new(
+        ComparisonValue::new(1, None),
+        [
+            (ComparisonOperator::Lesser, ComparisonValue::new(2, None)),
+            (ComparisonOperator::Lesser, ComparisonValue::new(3, None)),
+            (ComparisonOperator::Equal, ComparisonValue::new(3, None)),
+        ],
+    );
+    assert_eq!(cmp.to_string(), "`1 < 2 < 3 == 3` is true");
     ```
     */
     pub fn new(
-        first_val: ComparisonValue<T>,
-        comp_first_to_second: ComparisonOperator,
-        second_val: ComparisonValue<T>,
-        comp_second_to_third: ComparisonOperator,
-        third_val: Option<ComparisonValue<T>>,
-    ) -> Self {
-        let mut is_true = true;
-        if !comp_first_to_second.is_true(&first_val.value, &second_val.value) {
-            is_true = false;
+        first_val: ComparisonValue<L>,
+        links: impl IntoIterator<Item = (ComparisonOperator, ComparisonValue<R>)>,
+    ) -> Self
+    where
+        R: PartialOrd,
+    {
+        let mut other_vals: Vec<ComparisonValue<R>> = Vec::new();
+        let mut operators = Vec::new();
+        let mut link_results = Vec::new();
+        for (operator, value) in links {
+            let ordering = match other_vals.last() {
+                Some(previous) => previous.value.partial_cmp(&value.value),
+                None => first_val.value.partial_cmp(&value.value),
+            };
+            link_results.push(LinkResult {
+                ordering,
+                outcome: operator.outcome(ordering),
+                deviation: None,
+                tolerance: None,
+            });
+            operators.push(operator);
+            other_vals.push(value);
         }
-        if let Some(third_val) = third_val.as_ref() {
-            if !comp_second_to_third.is_true(&second_val.value, &third_val.value) {
-                is_true = false;
-            }
-        };
 
         return Self {
             first_val,
-            comp_first_to_second,
-            second_val,
-            comp_second_to_third,
-            third_val,
-            is_true,
+            other_vals: other_vals.into_boxed_slice(),
+            operators: operators.into_boxed_slice(),
+            link_results: link_results.into_boxed_slice(),
+            range: None,
+            location: None,
+            message: None,
         };
     }
 
@@ -174,10 +307,7 @@ impl<T: PartialOrd> Comparison<T> {
     fn smaller_than_zero(input: i32) -> Result<(), Comparison<i32>> {
         Comparison::new_checked(
             ComparisonValue::new(0, None),
-            ComparisonOperator::Greater,
-            ComparisonValue::new(input, Some("input")),
-            ComparisonOperator::Equal,
-            None,
+            [(ComparisonOperator::Greater, ComparisonValue::new(input, Some("input")))],
         )?;
         return Ok(());
     }
@@ -194,123 +324,956 @@ impl<T: PartialOrd> Comparison<T> {
 
     let ok = Comparison::new_checked(
         ComparisonValue::new(2, None),
-        ComparisonOperator::Greater,
-        ComparisonValue::new(0, None),
-        ComparisonOperator::Equal,
-        None,
+        [(ComparisonOperator::Greater, ComparisonValue::new(0, None))],
     );
     assert_eq!(ok.unwrap_or_else(|x| x).to_string(), "`2 > 0` is true");
 
     let err = Comparison::new_checked(
         ComparisonValue::new(2, None),
-        ComparisonOperator::Lesser,
-        ComparisonValue::new(0, None),
-        ComparisonOperator::Equal,
-        None,
+        [(ComparisonOperator::Lesser, ComparisonValue::new(0, None))],
     );
     assert_eq!(err.unwrap_or_else(|x| x).to_string(), "`2 < 0` is false");
     ```
      */
     pub fn new_checked(
-        first_val: ComparisonValue<T>,
-        comp_first_to_second: ComparisonOperator,
-        second_val: ComparisonValue<T>,
-        comp_second_to_third: ComparisonOperator,
-        third_val: Option<ComparisonValue<T>>,
-    ) -> Result<Self, Self> {
-        let this = Self::new(
+        first_val: ComparisonValue<L>,
+        links: impl IntoIterator<Item = (ComparisonOperator, ComparisonValue<R>)>,
+    ) -> Result<Self, Self>
+    where
+        R: PartialOrd,
+    {
+        return Self::new(first_val, links).into_result();
+    }
+
+    /**
+    Returns a new [`Comparison`] by checking whether two values are
+    approximately equal, i.e. whether their deviation is within the given
+    [`Tolerance`]. The resulting comparison uses the operator
+    [`ComparisonOperator::Equal`] with the tolerance attached to its link (see
+    [`Comparison::link_tolerance`]). It is displayed as `~=` and its message
+    contains both the deviation and the tolerance.
+
+    # Examples
+    ```
+    use compare_variables::{Comparison, ComparisonValue, Tolerance};
+
+    let cmp = Comparison::new_approx(
+        ComparisonValue::new(1.5, Some("x")),
+        Tolerance::Absolute(0.1),
+        ComparisonValue::new(1.0, None),
+    );
+    assert_eq!(
+        cmp.to_string(),
+        "`x (value: 1.5) ~= 1.0` is false (absolute deviation: 0.5, tolerance: 0.1)"
+    );
+
+    let cmp = Comparison::new_approx(
+        ComparisonValue::new(1.0, None),
+        Tolerance::Ulps(4),
+        ComparisonValue::new(1.0 + f64::EPSILON, None),
+    );
+    assert_eq!(
+        cmp.to_string(),
+        "`1.0 ~= 1.0000000000000002` is true (deviation: 1 ULPs, tolerance: 4 ULPs)"
+    );
+    ```
+    */
+    pub fn new_approx(
+        first_val: ComparisonValue<L>,
+        tolerance: Tolerance,
+        second_val: ComparisonValue<R>,
+    ) -> Self
+    where
+        L: ApproxEq<R>,
+    {
+        let deviation = first_val.value.deviation(&second_val.value, tolerance);
+        let link_result = LinkResult {
+            ordering: first_val.value.partial_cmp(&second_val.value),
+            outcome: tolerance.outcome(deviation),
+            deviation,
+            tolerance: Some(tolerance),
+        };
+        return Self {
             first_val,
-            comp_first_to_second,
-            second_val,
-            comp_second_to_third,
-            third_val,
-        );
+            other_vals: Box::new([second_val]),
+            operators: Box::new([ComparisonOperator::Equal]),
+            link_results: Box::new([link_result]),
+            range: None,
+            location: None,
+            message: None,
+        };
+    }
 
-        if this.is_true() {
-            return Ok(this);
-        } else {
-            return Err(this);
+    /**
+    Returns a new instance of [`Comparison`] created via
+    [`Comparison::new_approx`] and wraps it in [`Ok`] if [`Comparison::is_true`]
+    is true and in [`Err`] otherwise.
+     */
+    pub fn new_approx_checked(
+        first_val: ComparisonValue<L>,
+        tolerance: Tolerance,
+        second_val: ComparisonValue<R>,
+    ) -> Result<Self, Self>
+    where
+        L: ApproxEq<R>,
+    {
+        return Self::new_approx(first_val, tolerance, second_val).into_result();
+    }
+
+    /**
+    Returns the index of the value which is checked for range membership, if
+    the comparison has been created via [`Comparison::new_in_bounds`] or
+    [`Comparison::new_in_range`]. The other values of the chain are the bounds
+    of the range.
+     */
+    pub fn range_element(&self) -> Option<usize> {
+        return self.range.as_ref().map(|range| range.element());
+    }
+
+    /**
+    Sets the name of the range, which may be created at runtime, for a
+    comparison created via [`Comparison::new_in_range`]. Other comparisons are
+    returned unchanged.
+
+    ```
+    use compare_variables::{Comparison, ComparisonValue};
+
+    let valid = 0..=1;
+    let cmp = Comparison::new_in_range(ComparisonValue::new(2, Some("x")), &valid, None)
+        .with_range_name(format!("limits[{}]", 0));
+    assert_eq!(cmp.to_string(), "`x (value: 2) in limits[0] (value: 0..=1)` is false");
+    ```
+     */
+    pub fn with_range_name(mut self, name: impl Into<Cow<'static, str>>) -> Self {
+        if let Some(range) = self.range.as_mut() {
+            range.name = Some(name.into());
         }
+        return self;
+    }
+
+    /**
+    Returns the deviation between the value at `index` and its successor, if
+    they have been compared approximately (see [`Comparison::new_approx`]).
+     */
+    pub fn link_deviation(&self, index: usize) -> Option<f64> {
+        return self.link_results.get(index)?.deviation;
+    }
+
+    /**
+    Returns the tolerance of the link between the value at `index` and its
+    successor, if they have been compared approximately (see
+    [`Comparison::new_approx`]).
+     */
+    pub fn link_tolerance(&self, index: usize) -> Option<Tolerance> {
+        return self.link_results.get(index)?.tolerance;
+    }
+
+    /**
+    Returns the number of values in the chain. This is always one more than the
+    number of operators.
+     */
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        return self.other_vals.len() + 1;
     }
 
     /**
     Returns a reference to the first value.
      */
-    pub fn first_val(&self) -> &ComparisonValue<T> {
+    pub fn first_val(&self) -> &ComparisonValue<L> {
         return &self.first_val;
     }
 
     /**
-    Returns a reference to the second value.
+    Returns all values of the chain except for the first one in order. The
+    value at index `i` of the chain is therefore found at index `i - 1` of the
+    returned slice.
      */
-    pub fn second_val(&self) -> &ComparisonValue<T> {
-        return &self.second_val;
+    pub fn other_vals(&self) -> &[ComparisonValue<R>] {
+        return &self.other_vals;
     }
 
     /**
-    Returns a reference to the third value, if one was given.
+    Returns the comparison operator between the value at `index` and its
+    successor, if it exists.
      */
-    pub fn third_val(&self) -> Option<&ComparisonValue<T>> {
-        return self.third_val.as_ref();
+    pub fn operator(&self, index: usize) -> Option<ComparisonOperator> {
+        return self.operators.get(index).copied();
     }
 
     /**
-    Returns the comparison operator between the first and second value.
+    Returns all comparison operators of the chain in order. The operator at
+    index `i` compares the values at indices `i` and `i + 1`.
      */
-    pub fn comp_first_to_second(&self) -> ComparisonOperator {
-        return self.comp_first_to_second;
+    pub fn operators(&self) -> &[ComparisonOperator] {
+        return &self.operators;
     }
 
     /**
-    Returns the comparison operator between the second and third value.
+    Returns the outcome of the entire comparison.
+
+    To evaluate the comparison, each value of the chain is compared to its
+    successor using the operator between them (see [`Comparison::link_outcome`]).
+    If any of these individual comparisons is [`ComparisonOutcome::Incomparable`],
+    the entire comparison is incomparable as well. Otherwise, the comparison is
+    [`ComparisonOutcome::False`] if any individual comparison is false and
+    [`ComparisonOutcome::True`] if all individual comparisons are true.
+
+    ```
+    use compare_variables::{compare_variables, ComparisonOutcome};
+
+    let x = f64::NAN;
+    let cmp = compare_variables!(x > 0.0).unwrap_err();
+    assert_eq!(cmp.outcome(), ComparisonOutcome::Incomparable);
+    assert_eq!(cmp.to_string(), "`x (value: NaN) > 0.0` is undecidable: values are not comparable");
+
+    let x = -1.0;
+    let cmp = compare_variables!(x > 0.0).unwrap_err();
+    assert_eq!(cmp.outcome(), ComparisonOutcome::False);
+    ```
      */
-    pub fn comp_second_to_third(&self) -> ComparisonOperator {
-        return self.comp_second_to_third;
+    pub fn outcome(&self) -> ComparisonOutcome {
+        let mut outcome = ComparisonOutcome::True;
+        for link in self.link_results.iter() {
+            match link.outcome {
+                ComparisonOutcome::Incomparable => return ComparisonOutcome::Incomparable,
+                ComparisonOutcome::False => outcome = ComparisonOutcome::False,
+                ComparisonOutcome::True => (),
+            }
+        }
+        return outcome;
     }
 
     /**
-    Returns whether the comparison evaluates to true or false.
+    Returns whether the comparison evaluates to true.
 
-    To evaluate the comparison, [`Comparison::first_val`] is compared to
-    [`Comparison::second_val`] using the [`Comparison::comp_first_to_second`]
-    operator. If a [`Comparison::third_val`] has been given, it is compared to
-    [`Comparison::second_val`] using the [`Comparison::comp_second_to_third`]
-    operator. If both these individual comparisons return true, this method
-    returns true as well.
+    This is the case if [`Comparison::outcome`] is [`ComparisonOutcome::True`].
+    Incomparable values (e.g. NaN) therefore lead to this method returning false.
 
     This method is used within [`Comparison::new_checked`] to decide whether the
     [`Comparison`] should be wrapped in [`Ok`] or [`Err`].
      */
     pub fn is_true(&self) -> bool {
-        self.is_true
+        return self.outcome() == ComparisonOutcome::True;
+    }
+
+    /**
+    Returns whether any values of the chain could not be compared with each
+    other (e.g. because one of them is NaN).
+     */
+    pub fn is_incomparable(&self) -> bool {
+        return self.outcome() == ComparisonOutcome::Incomparable;
+    }
+
+    /**
+    Returns the outcome of the individual comparison ("link") between the value
+    at `index` and its successor. If no link exists at `index`, [`None`] is
+    returned.
+     */
+    pub fn link_outcome(&self, index: usize) -> Option<ComparisonOutcome> {
+        return self.link_results.get(index).map(|link| link.outcome);
+    }
+
+    /**
+    Returns the result of [`PartialOrd::partial_cmp`] for the value at `index`
+    and its successor. If no link exists at `index`, [`None`] is returned.
+
+    ```
+    use std::cmp::Ordering;
+    use compare_variables::compare_variables;
+
+    let cmp = compare_variables!(1.0 < 2.0 < f64::NAN).unwrap_err();
+    assert_eq!(cmp.link_ordering(0), Some(Some(Ordering::Less)));
+    assert_eq!(cmp.link_ordering(1), Some(None));
+    assert_eq!(cmp.link_ordering(2), None);
+    ```
+     */
+    pub fn link_ordering(&self, index: usize) -> Option<Option<std::cmp::Ordering>> {
+        return self.link_results.get(index).map(|link| link.ordering);
+    }
+
+    /**
+    Returns whether the individual comparison ("link") between the value at
+    `index` and its successor evaluates to true or false. If no link exists at
+    `index`, [`None`] is returned.
+
+    This can be used to find out which part of a chained comparison failed:
+
+    ```
+    use compare_variables::compare_variables;
+
+    let x = 2;
+    let cmp = compare_variables!(0 < x <= 1).unwrap_err();
+    assert_eq!(cmp.link_is_true(0), Some(true)); // 0 < x
+    assert_eq!(cmp.link_is_true(1), Some(false)); // x <= 1
+    assert_eq!(cmp.link_is_true(2), None);
+    ```
+     */
+    pub fn link_is_true(&self, index: usize) -> Option<bool> {
+        return self
+            .link_outcome(index)
+            .map(|outcome| outcome == ComparisonOutcome::True);
+    }
+
+    /**
+    Returns the indices of all links which do not evaluate to true (i.e. which
+    are either false or incomparable). The link at index
+    `i` compares the values at indices `i` and `i + 1` using the operator at
+    index `i`.
+
+    ```
+    use compare_variables::compare_variables;
+
+    let x = -1;
+    let cmp = compare_variables!(0 < x <= 1).unwrap_err();
+    assert_eq!(cmp.failed_links().collect::<Vec<_>>(), vec![0]);
+
+    let cmp = compare_variables!(3 < 2 < 1).unwrap_err();
+    assert_eq!(cmp.failed_links().collect::<Vec<_>>(), vec![0, 1]);
+    ```
+     */
+    pub fn failed_links(&self) -> impl Iterator<Item = usize> + '_ {
+        return self
+            .link_results
+            .iter()
+            .enumerate()
+            .filter_map(|(index, link)| {
+                (link.outcome != ComparisonOutcome::True).then_some(index)
+            });
+    }
+
+    /**
+    Attaches the location in the source code where the comparison has been
+    created. The macro [`compare_variables`] does this automatically.
+
+    # Examples
+    ```
+    use compare_variables::{Comparison, ComparisonValue, ComparisonOperator, SourceLocation};
+
+    static LOCATION: SourceLocation = SourceLocation::new("src/lib.rs", 3, 14, "my_crate");
+
+    let cmp = Comparison::new(
+        ComparisonValue::new(1, Some("x")),
+        [(ComparisonOperator::Greater, ComparisonValue::new(2, None))],
+    )
+    .with_location(&LOCATION);
+    assert_eq!(cmp.location(), Some(&LOCATION));
+    assert_eq!(format!("{cmp}"), "`x (value: 1) > 2` is false");
+    assert_eq!(format!("{cmp:#}"), "`x (value: 1) > 2` is false at src/lib.rs:3:14");
+    ```
+     */
+    pub fn with_location(mut self, location: &'static SourceLocation) -> Self {
+        self.location = Some(location);
+        return self;
+    }
+
+    /**
+    Returns the location in the source code where the comparison has been
+    created, if it is known (see [`Comparison::with_location`]).
+     */
+    pub fn location(&self) -> Option<&'static SourceLocation> {
+        return self.location;
+    }
+
+    /**
+    Attaches a custom message to the comparison, which is appended to the
+    generated message when displaying the comparison. The macro
+    [`compare_variables`] does this if a message in the [`format!`] syntax is
+    given after the comparison. In that case, the message is only formatted if
+    the comparison does not evaluate to true.
+
+    # Examples
+    ```
+    use compare_variables::{Comparison, ComparisonValue, ComparisonOperator};
+
+    let cmp = Comparison::new(
+        ComparisonValue::new(4, Some("retries")),
+        [(ComparisonOperator::LesserOrEqual, ComparisonValue::new(3, Some("MAX")))],
+    )
+    .with_message("retry budget exhausted");
+    assert_eq!(cmp.message(), Some("retry budget exhausted"));
+    assert_eq!(
+        cmp.to_string(),
+        "`retries (value: 4) <= MAX (value: 3)` is false: retry budget exhausted"
+    );
+    ```
+     */
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into().into_boxed_str());
+        return self;
+    }
+
+    /**
+    Returns the custom message of the comparison, if one has been attached
+    (see [`Comparison::with_message`]).
+     */
+    pub fn message(&self) -> Option<&str> {
+        return self.message.as_deref();
+    }
+
+    /**
+    Wraps the comparison in [`Ok`] if [`Comparison::is_true`] is true and in
+    [`Err`] otherwise.
+     */
+    pub fn into_result(self) -> Result<Self, Self> {
+        if self.is_true() {
+            return Ok(self);
+        } else {
+            return Err(self);
+        }
+    }
+}
+
+impl<T: PartialOrd> Comparison<T, T> {
+    /**
+    Returns a new [`Comparison`] by checking whether `value` lies within the
+    range defined by the `start` and `end` bounds.
+
+    The range membership is represented as a chain: An included bound is
+    compared with [`ComparisonOperator::LesserOrEqual`], an excluded bound with
+    [`ComparisonOperator::Lesser`] and an unbounded side is omitted. For
+    example, `x in lo..hi` is represented by the chain `lo <= x < hi`. The
+    message however uses the range notation.
+
+    # Examples
+    ```
+    use std::ops::Bound;
+    use compare_variables::{Comparison, ComparisonValue, ComparisonOperator};
+
+    let cmp = Comparison::new_in_bounds(
+        ComparisonValue::new(2, Some("x")),
+        Bound::Included(ComparisonValue::new(0, None)),
+        Bound::Included(ComparisonValue::new(1, None)),
+    );
+    assert_eq!(cmp.to_string(), "`x (value: 2) in 0..=1` is false");
+    assert_eq!(cmp.operators(), &[ComparisonOperator::LesserOrEqual, ComparisonOperator::LesserOrEqual]);
+    assert_eq!(cmp.range_element(), Some(1));
+
+    let cmp = Comparison::new_in_bounds(
+        ComparisonValue::new(2, Some("x")),
+        Bound::Unbounded,
+        Bound::Excluded(ComparisonValue::new(10, Some("max"))),
+    );
+    assert_eq!(cmp.to_string(), "`x (value: 2) in ..max (value: 10)` is true");
+    ```
+
+    Rust has no notation for ranges with an excluded start bound. Such
+    comparisons are therefore displayed as a chain:
+    ```
+    use std::ops::Bound;
+    use compare_variables::{Comparison, ComparisonValue};
+
+    let cmp = Comparison::new_in_bounds(
+        ComparisonValue::new(0, Some("x")),
+        Bound::Excluded(ComparisonValue::new(0, None)),
+        Bound::Unbounded,
+    );
+    assert_eq!(cmp.to_string(), "`0 < x (value: 0)` is false");
+    ```
+    */
+    pub fn new_in_bounds(
+        value: ComparisonValue<T>,
+        start: Bound<ComparisonValue<T>>,
+        end: Bound<ComparisonValue<T>>,
+    ) -> Self {
+        let (first_val, mut links, has_start) = match start {
+            Bound::Included(start) => (
+                start,
+                vec![(ComparisonOperator::LesserOrEqual, value)],
+                true,
+            ),
+            Bound::Excluded(start) => (start, vec![(ComparisonOperator::Lesser, value)], true),
+            Bound::Unbounded => (value, Vec::new(), false),
+        };
+        match end {
+            Bound::Included(end) => links.push((ComparisonOperator::LesserOrEqual, end)),
+            Bound::Excluded(end) => links.push((ComparisonOperator::Lesser, end)),
+            Bound::Unbounded => (),
+        }
+
+        let mut this = Self::new(first_val, links);
+        this.range = Some(Box::new(RangeMembership {
+            has_start,
+            name: None,
+        }));
+        return this;
+    }
+
+    /**
+    Returns a new instance of [`Comparison`] created via
+    [`Comparison::new_in_bounds`] and wraps it in [`Ok`] if
+    [`Comparison::is_true`] is true and in [`Err`] otherwise.
+     */
+    pub fn new_in_bounds_checked(
+        value: ComparisonValue<T>,
+        start: Bound<ComparisonValue<T>>,
+        end: Bound<ComparisonValue<T>>,
+    ) -> Result<Self, Self> {
+        return Self::new_in_bounds(value, start, end).into_result();
+    }
+
+    /**
+    Returns a new [`Comparison`] by checking whether `value` lies within
+    `range`, which can be any type implementing [`RangeBounds`] (e.g.
+    [`std::ops::Range`] or [`std::ops::RangeInclusive`]). If `range_name` is
+    given, it is included in the message.
+
+    This is a shorthand for [`Comparison::new_in_bounds`] with the bounds of
+    `range`.
+
+    # Examples
+    ```
+    use compare_variables::{Comparison, ComparisonValue};
+
+    const VALID: std::ops::RangeInclusive<i32> = 0..=1;
+
+    let cmp = Comparison::new_in_range(ComparisonValue::new(2, Some("x")), &VALID, Some("VALID"));
+    assert_eq!(cmp.to_string(), "`x (value: 2) in VALID (value: 0..=1)` is false");
+
+    let cmp = Comparison::new_in_range(ComparisonValue::new(2, Some("x")), &(5..), None);
+    assert_eq!(cmp.to_string(), "`x (value: 2) in 5..` is false");
+    ```
+    */
+    pub fn new_in_range<R: RangeBounds<T> + ?Sized>(
+        value: ComparisonValue<T>,
+        range: &R,
+        range_name: Option<&'static str>,
+    ) -> Self
+    where
+        T: Clone,
+    {
+        fn to_bound<T: PartialOrd + Clone>(bound: Bound<&T>) -> Bound<ComparisonValue<T>> {
+            return bound.map(|value| ComparisonValue::new(value.clone(), None));
+        }
+
+        let mut this = Self::new_in_bounds(
+            value,
+            to_bound(range.start_bound()),
+            to_bound(range.end_bound()),
+        );
+        if let Some(range) = this.range.as_mut() {
+            range.name = range_name.map(Cow::Borrowed);
+        }
+        return this;
+    }
+
+    /**
+    Returns a new instance of [`Comparison`] created via
+    [`Comparison::new_in_range`] and wraps it in [`Ok`] if
+    [`Comparison::is_true`] is true and in [`Err`] otherwise.
+     */
+    pub fn new_in_range_checked<R: RangeBounds<T> + ?Sized>(
+        value: ComparisonValue<T>,
+        range: &R,
+        range_name: Option<&'static str>,
+    ) -> Result<Self, Self>
+    where
+        T: Clone,
+    {
+        return Self::new_in_range(value, range, range_name).into_result();
+    }
+
+    /**
+    Returns a reference to the value at `index` within the chain, if it exists.
+
+    This method is only available if all values of the chain have the same
+    type. Otherwise, use [`Comparison::first_val`] and
+    [`Comparison::other_vals`].
+     */
+    pub fn value(&self, index: usize) -> Option<&ComparisonValue<T>> {
+        if index == 0 {
+            return Some(&self.first_val);
+        } else {
+            return self.other_vals.get(index - 1);
+        }
+    }
+
+    /**
+    Returns all values of the chain in order.
+
+    This method is only available if all values of the chain have the same
+    type. Otherwise, use [`Comparison::first_val`] and
+    [`Comparison::other_vals`].
+     */
+    pub fn values(&self) -> impl Iterator<Item = &ComparisonValue<T>> + '_ {
+        return std::iter::once(&self.first_val).chain(self.other_vals.iter());
     }
 }
 
-impl<T: PartialOrd + std::fmt::Debug> std::error::Error for Comparison<T> {}
+impl<'a, T: PartialOrd + ?Sized> Comparison<&'a T, &'a T> {
+    /**
+    Returns a new [`Comparison`] by checking whether the borrowed `value` lies
+    within `range`. Contrary to [`Comparison::new_in_range`], the bounds of
+    `range` are borrowed instead of being cloned.
 
-impl<T: PartialOrd + std::fmt::Debug> std::fmt::Debug for Comparison<T> {
+    # Examples
+    ```
+    use compare_variables::{Comparison, ComparisonValue};
+
+    let name = String::from("delta");
+    let range = String::from("alpha")..String::from("charlie");
+    let cmp = Comparison::new_in_borrowed_range(ComparisonValue::new(&name, Some("name")), &range, None);
+    assert_eq!(cmp.to_string(), "`name (value: \"delta\") in \"alpha\"..\"charlie\"` is false");
+    ```
+    */
+    pub fn new_in_borrowed_range<R: RangeBounds<T> + ?Sized>(
+        value: ComparisonValue<&'a T>,
+        range: &'a R,
+        range_name: Option<&'static str>,
+    ) -> Self {
+        fn to_bound<T: ?Sized>(bound: Bound<&T>) -> Bound<ComparisonValue<&T>> {
+            return bound.map(|value| ComparisonValue::new(value, None));
+        }
+
+        let mut this = Self::new_in_bounds(
+            value,
+            to_bound(range.start_bound()),
+            to_bound(range.end_bound()),
+        );
+        if let Some(range) = this.range.as_mut() {
+            range.name = range_name.map(Cow::Borrowed);
+        }
+        return this;
+    }
+
+    /**
+    Returns a new instance of [`Comparison`] created via
+    [`Comparison::new_in_borrowed_range`] and wraps it in [`Ok`] if
+    [`Comparison::is_true`] is true and in [`Err`] otherwise.
+     */
+    pub fn new_in_borrowed_range_checked<R: RangeBounds<T> + ?Sized>(
+        value: ComparisonValue<&'a T>,
+        range: &'a R,
+        range_name: Option<&'static str>,
+    ) -> Result<Self, Self> {
+        return Self::new_in_borrowed_range(value, range, range_name).into_result();
+    }
+}
+
+impl<L: PartialOrd<R> + Clone, R: Clone> Comparison<&L, &R> {
+    /**
+    Converts a comparison of borrowed values into a comparison which owns
+    clones of the values. This is needed if the comparison should outlive the
+    compared values, e.g. if it is returned from a function as an error.
+
+    The macro [`compare_variables`] borrows its operands by default, so this
+    method (or the equivalent [`From`] implementation used by the `?`
+    operator) converts its output into a `Comparison<T>`:
+
+    ```
+    use compare_variables::{compare_variables, Comparison};
+
+    fn non_empty(name: String) -> Result<String, Comparison<String>> {
+        let empty = String::new();
+        compare_variables!(name != empty)?;
+        return Ok(name);
+    }
+    assert_eq!(
+        non_empty(String::new()).unwrap_err().to_string(),
+        "`name (value: \"\") != empty (value: \"\")` is false"
+    );
+
+    let cmp: Comparison<Vec<i32>> = {
+        let v = vec![1, 2];
+        compare_variables!(v == vec![1, 2, 3]).unwrap_err().into_owned()
+    };
+    assert_eq!(cmp.to_string(), "`v (value: [1, 2]) == vec![1, 2, 3] (value: [1, 2, 3])` is false");
+    ```
+     */
+    pub fn into_owned(self) -> Comparison<L, R> {
+        return Comparison {
+            first_val: ComparisonValue {
+                value: self.first_val.value.clone(),
+                variable_name: self.first_val.variable_name,
+            },
+            other_vals: self
+                .other_vals
+                .into_vec()
+                .into_iter()
+                .map(|value| ComparisonValue {
+                    value: value.value.clone(),
+                    variable_name: value.variable_name,
+                })
+                .collect(),
+            operators: self.operators,
+            link_results: self.link_results,
+            range: self.range,
+            location: self.location,
+            message: self.message,
+        };
+    }
+}
+
+impl<L: PartialOrd<R> + Clone, R: Clone> From<Comparison<&L, &R>> for Comparison<L, R> {
+    fn from(value: Comparison<&L, &R>) -> Self {
+        return value.into_owned();
+    }
+}
+
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> std::error::Error
+    for Comparison<L, R>
+{
+}
+
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> std::fmt::Debug for Comparison<L, R> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         return std::fmt::Display::fmt(self, f);
     }
 }
 
-impl<T: PartialOrd + std::fmt::Debug> std::fmt::Display for Comparison<T> {
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> Comparison<L, R> {
+    /// Returns the value at `index` within the chain as a displayable object.
+    fn display_val(&self, index: usize) -> &dyn std::fmt::Display {
+        if index == 0 {
+            return &self.first_val;
+        } else {
+            return &self.other_vals[index - 1];
+        }
+    }
+
+    /// Returns the symbol of the link at `index`, which is `~=` for approximate links.
+    fn link_symbol(&self, index: usize) -> &'static str {
+        if self.link_results[index].tolerance.is_some() {
+            return "~=";
+        } else {
+            return self.operators[index].as_str();
+        }
+    }
+
+    /**
+    Writes a range membership in the notation `x in lo..=hi`. Returns `None` if
+    the range has an excluded start bound, which cannot be written in this
+    notation.
+     */
+    fn fmt_range_membership(
+        &self,
+        range: &RangeMembership,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> Option<std::fmt::Result> {
+        if range.has_start && self.operators[0] == ComparisonOperator::Lesser {
+            return None;
+        }
+        let mut write = || -> std::fmt::Result {
+            write!(f, "{} in ", self.display_val(range.element()))?;
+            if let Some(name) = range.name.as_deref() {
+                write!(f, "{name} (value: ")?;
+            }
+            if range.has_start {
+                write!(f, "{}", self.first_val)?;
+            }
+            if range.element() < self.operators.len() {
+                let end = self.display_val(range.element() + 1);
+                match self.operators[range.element()] {
+                    ComparisonOperator::LesserOrEqual => write!(f, "..={end}")?,
+                    _ => write!(f, "..{end}")?,
+                }
+            } else {
+                write!(f, "..")?;
+            }
+            if range.name.is_some() {
+                write!(f, ")")?;
+            }
+            return Ok(());
+        };
+        return Some(write());
+    }
+}
+
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> std::fmt::Display
+    for Comparison<L, R>
+{
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "`{} {} {}",
-            self.first_val, self.comp_first_to_second, self.second_val
-        )?;
+        return self.fmt_with_style(default_style(), f);
+    }
+}
+
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> Comparison<L, R> {
+    /**
+    Returns a type-erased view of the comparison, which is used to implement a
+    [`MessageStyle`].
+     */
+    pub fn view(&self) -> ComparisonView<'_> {
+        return ComparisonView::new(self);
+    }
+
+    /**
+    Returns an object which displays the comparison using the given `style`
+    instead of the [default style](default_style).
+
+    # Examples
+    ```
+    use compare_variables::{compare_variables, NaturalLanguageStyle};
+
+    let x = 3;
+    let err = compare_variables!(x > 10).unwrap_err();
+    assert_eq!(err.to_string(), "`x (value: 3) > 10` is false");
+    assert_eq!(
+        err.display_with(&NaturalLanguageStyle).to_string(),
+        "x must be greater than 10, but was 3"
+    );
+    ```
+     */
+    pub fn display_with<'a>(&'a self, style: &'a dyn MessageStyle) -> impl std::fmt::Display + 'a {
+        return StyledComparison {
+            comparison: self,
+            style,
+        };
+    }
 
-        if let Some(third_val) = self.third_val.as_ref() {
-            write!(f, " {} {}", self.comp_second_to_third, third_val)?;
+    /// Writes the message in the given style, followed by the custom message
+    /// and (for the alternate format `{:#}`) the source location.
+    fn fmt_with_style(
+        &self,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        style.fmt(&self.view(), f)?;
+        if let Some(message) = self.message.as_ref() {
+            write!(f, ": {message}")?;
         }
-        if self.is_true() {
-            write!(f, "` is true")
+
+        // The alternate format `{:#}` appends the source location
+        if let (true, Some(location)) = (f.alternate(), self.location) {
+            write!(f, " at {location}")?;
+        }
+        return Ok(());
+    }
+}
+
+/// A [`Comparison`] which is displayed in a specific [`MessageStyle`].
+struct StyledComparison<'a, L: PartialOrd<R>, R> {
+    comparison: &'a Comparison<L, R>,
+    style: &'a dyn MessageStyle,
+}
+
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> std::fmt::Display
+    for StyledComparison<'_, L, R>
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return self.comparison.fmt_with_style(self.style, f);
+    }
+}
+
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> style::ErasedComparison
+    for Comparison<L, R>
+{
+    fn len(&self) -> usize {
+        return Comparison::len(self);
+    }
+
+    fn value(&self, index: usize) -> Option<&dyn std::fmt::Debug> {
+        if index == 0 {
+            return Some(&self.first_val.value);
         } else {
-            write!(f, "` is false")
+            return self
+                .other_vals
+                .get(index - 1)
+                .map(|value| &value.value as &dyn std::fmt::Debug);
         }
     }
+
+    fn variable_name(&self, index: usize) -> Option<&str> {
+        if index == 0 {
+            return self.first_val.variable_name.as_deref();
+        } else {
+            return self.other_vals.get(index - 1)?.variable_name.as_deref();
+        }
+    }
+
+    fn operator(&self, index: usize) -> Option<ComparisonOperator> {
+        return Comparison::operator(self, index);
+    }
+
+    fn outcome(&self) -> ComparisonOutcome {
+        return Comparison::outcome(self);
+    }
+
+    fn link_outcome(&self, index: usize) -> Option<ComparisonOutcome> {
+        return Comparison::link_outcome(self, index);
+    }
+
+    fn link_deviation(&self, index: usize) -> Option<f64> {
+        return Comparison::link_deviation(self, index);
+    }
+
+    fn link_tolerance(&self, index: usize) -> Option<Tolerance> {
+        return Comparison::link_tolerance(self, index);
+    }
+
+    fn range_element(&self) -> Option<usize> {
+        return Comparison::range_element(self);
+    }
+
+    /// Writes the message of [`ExpressionStyle`].
+    fn fmt_expression(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`")?;
+        let range_membership = match self.range.as_ref() {
+            Some(range) => self.fmt_range_membership(range, f).transpose()?.is_some(),
+            None => false,
+        };
+        if !range_membership {
+            write!(f, "{}", self.first_val)?;
+            for (index, value) in self.other_vals.iter().enumerate() {
+                write!(f, " {} {value}", self.link_symbol(index))?;
+            }
+        }
+        match self.outcome() {
+            ComparisonOutcome::True => write!(f, "` is true")?,
+            ComparisonOutcome::False => write!(f, "` is false")?,
+            ComparisonOutcome::Incomparable => {
+                write!(f, "` is undecidable: values are not comparable")?
+            }
+        }
+
+        // Report the deviation of approximate comparisons
+        for link in self.link_results.iter() {
+            if let (Some(tolerance), Some(deviation)) = (link.tolerance, link.deviation) {
+                write!(f, " (")?;
+                tolerance.fmt_deviation(deviation, f)?;
+                write!(f, ")")?;
+            }
+        }
+        if self.is_true() {
+            return Ok(());
+        }
+
+        // Highlight the failed links of chained comparisons
+        if self.operators.len() > 1 && !range_membership {
+            write!(f, " (failed: ")?;
+            for (n, index) in self.failed_links().enumerate() {
+                if n > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(
+                    f,
+                    "`{} {} {}`",
+                    self.display_val(index),
+                    self.link_symbol(index),
+                    self.display_val(index + 1)
+                )?;
+            }
+            write!(f, ")")?;
+        }
+        return Ok(());
+    }
+}
+
+/**
+The outcome of a [`Comparison`] or of an individual link within a comparison
+chain.
+
+Besides being true or false, a comparison can also be undecidable if the
+compared values have no ordering with respect to each other, i.e. if
+[`PartialOrd::partial_cmp`] returns [`None`]. The most common example for this
+are NaN floating point values.
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum ComparisonOutcome {
+    True,
+    False,
+    Incomparable,
 }
 
 /**
@@ -319,24 +1282,44 @@ Wrapper around the value with an additional optional field for the variable name
 If a variable name is given, it is used in constructing the error message of [`Comparison`] in addition to the value itself.
  */
 #[derive(Debug, Clone)]
-pub struct ComparisonValue<T: PartialOrd> {
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ComparisonValue<T> {
     pub value: T,
-    pub variable_name: Option<&'static str>,
+    pub variable_name: Option<Cow<'static, str>>,
 }
 
-impl<T: PartialOrd> ComparisonValue<T> {
+impl<T> ComparisonValue<T> {
     /**
     Returns a new instance of [`ComparisonValue`].
      */
     pub fn new(value: T, variable_name: Option<&'static str>) -> Self {
         return Self {
             value,
-            variable_name,
+            variable_name: variable_name.map(Cow::Borrowed),
+        };
+    }
+
+    /**
+    Returns a new instance of [`ComparisonValue`] with a variable name, which
+    may be created at runtime.
+
+    ```
+    use compare_variables::ComparisonValue;
+
+    let index = 3;
+    let value = ComparisonValue::named(1.5, format!("values[{index}]"));
+    assert_eq!(value.to_string(), "values[3] (value: 1.5)");
+    ```
+     */
+    pub fn named(value: T, variable_name: impl Into<Cow<'static, str>>) -> Self {
+        return Self {
+            value,
+            variable_name: Some(variable_name.into()),
         };
     }
 }
 
-impl<T: PartialOrd + std::fmt::Debug> std::fmt::Display for ComparisonValue<T> {
+impl<T: std::fmt::Debug> std::fmt::Display for ComparisonValue<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(name) = self.variable_name.as_ref() {
             write!(f, "{name} (value: ")?;
@@ -354,7 +1337,7 @@ Defines a comparison between two values.
 See the docstring of [`ComparisonOperator::is_true`] for an example on how to apply this enum for a comparison.
  */
 #[repr(u8)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ComparisonOperator {
     Lesser,
     LesserOrEqual,
@@ -368,7 +1351,7 @@ impl ComparisonOperator {
     /**
     Formats the enum value into a static string slice.
      */
-    pub fn as_str(&self) -> &'static str {
+    pub const fn as_str(&self) -> &'static str {
         match self {
             ComparisonOperator::Lesser => "<",
             ComparisonOperator::LesserOrEqual => "<=",
@@ -379,10 +1362,65 @@ impl ComparisonOperator {
         }
     }
 
+    /**
+    Returns whether two values with the given `ordering` satisfy the operator.
+    If `ordering` is [`None`] (the values are not comparable),
+    [`ComparisonOutcome::Incomparable`] is returned.
+
+    ```
+    use std::cmp::Ordering;
+    use compare_variables::{ComparisonOperator, ComparisonOutcome};
+
+    assert_eq!(ComparisonOperator::Lesser.outcome(Some(Ordering::Less)), ComparisonOutcome::True);
+    assert_eq!(ComparisonOperator::Inequal.outcome(Some(Ordering::Equal)), ComparisonOutcome::False);
+    assert_eq!(ComparisonOperator::Equal.outcome(1.0.partial_cmp(&f64::NAN)), ComparisonOutcome::Incomparable);
+    ```
+     */
+    pub fn outcome(&self, ordering: Option<std::cmp::Ordering>) -> ComparisonOutcome {
+        let Some(ordering) = ordering else {
+            return ComparisonOutcome::Incomparable;
+        };
+        if self.is_true_for_ordering(ordering) {
+            return ComparisonOutcome::True;
+        } else {
+            return ComparisonOutcome::False;
+        }
+    }
+
+    /**
+    Returns whether two values with the given `ordering` satisfy the operator.
+    Contrary to [`ComparisonOperator::outcome`], this method can be used in
+    const contexts. Together with [`ConstValue::ordering`], it is the const
+    counterpart to [`ComparisonOperator::is_true`] for primitive types.
+
+    ```
+    use compare_variables::{ComparisonOperator, ConstValue};
+
+    const IS_TRUE: bool =
+        ComparisonOperator::LesserOrEqual.is_true_for_ordering(ConstValue(-1i64).ordering(2));
+    assert!(IS_TRUE);
+    ```
+     */
+    pub const fn is_true_for_ordering(&self, ordering: std::cmp::Ordering) -> bool {
+        use std::cmp::Ordering;
+        match self {
+            ComparisonOperator::Lesser => return matches!(ordering, Ordering::Less),
+            ComparisonOperator::LesserOrEqual => return !matches!(ordering, Ordering::Greater),
+            ComparisonOperator::Equal => return matches!(ordering, Ordering::Equal),
+            ComparisonOperator::Inequal => return !matches!(ordering, Ordering::Equal),
+            ComparisonOperator::GreaterOrEqual => return !matches!(ordering, Ordering::Less),
+            ComparisonOperator::Greater => return matches!(ordering, Ordering::Greater),
+        }
+    }
+
     /**
     Compares the ordering of two values.
 
     Returns the output of the following comparison: `first_val self second_val`.
+    Contrary to [`Comparison`], this method uses the operators of [`PartialOrd`]
+    and [`PartialEq`] directly. Incomparable values therefore always return
+    false, except for [`ComparisonOperator::Inequal`] (e.g. `NaN != 1.0` is
+    true).
     This is best illustrated via the following examples:
     ```
     use compare_variables::ComparisonOperator;
@@ -392,7 +1430,11 @@ impl ComparisonOperator {
     assert!(!ComparisonOperator::Greater.is_true(&-1i32, &1i32));
     ```
      */
-    pub fn is_true<T: PartialOrd>(&self, first_val: &T, second_val: &T) -> bool {
+    pub fn is_true<L: PartialOrd<R> + ?Sized, R: ?Sized>(
+        &self,
+        first_val: &L,
+        second_val: &R,
+    ) -> bool {
         match self {
             ComparisonOperator::Lesser => return first_val < second_val,
             ComparisonOperator::LesserOrEqual => return first_val <= second_val,
@@ -423,78 +1465,235 @@ impl std::fmt::Display for ComparisonOperator {
 }
 
 /**
-This trait is used to abstract a concrete `Comparison<T>` as a trait object where `T` is erased.
+This trait is used to abstract a concrete `Comparison<L, R>` as a trait object where `L` and `R` are erased.
 
 As an example for using this trait, let's assume a function performs two comparisons on different types
-but only wants to return a single error type:
+but only wants to return a single error type. The wrapper [`AnyComparison`] can be created from
+every comparison via the `?` operator:
 ```
-use compare_variables::{compare_variables, ComparisonErrorTrait};
+use compare_variables::{compare_variables, AnyComparison};
 
-fn example() -> Result<(), Box<dyn ComparisonErrorTrait>> {
-    compare_variables!(1 > 2).map_err(|e| Box::new(e) as Box<dyn ComparisonErrorTrait>)?;
-    compare_variables!(1.0 > 2.0).map_err(|e| Box::new(e) as Box<dyn ComparisonErrorTrait>)?;
+fn example(count: i32, ratio: f64) -> Result<(), AnyComparison> {
+    compare_variables!(count > 2)?;
+    compare_variables!(ratio > 2.0)?;
     return Ok(());
 }
+assert_eq!(example(1, 3.0).unwrap_err().to_string(), "`count (value: 1) > 2` is false");
 ```
  */
+#[allow(clippy::len_without_is_empty)]
 pub trait ComparisonErrorTrait: std::error::Error + Sync + Send + std::any::Any {
     /**
-    Writes the representation of the first comparison value into the given formatter.
-    This function is especially useful if a [`Comparison`] is used as a trait
-    object [`ComparisonErrorTrait`] in order to erase the underlying type.
+    Returns the number of values in the comparison chain.
+     */
+    fn len(&self) -> usize;
+
+    /**
+    Returns the outcome of the entire comparison, see [`Comparison::outcome`].
+     */
+    fn outcome(&self) -> ComparisonOutcome;
+
+    /**
+    Returns true if the comparison is true, see [`Comparison::is_true`].
      */
-    fn fmt_first_val(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn is_true(&self) -> bool {
+        return self.outcome() == ComparisonOutcome::True;
+    }
+
+    /**
+    Returns the variable name of the value at `index`, if the value exists and
+    has a name.
+     */
+    fn variable_name(&self, index: usize) -> Option<&str>;
 
     /**
-    Writes the representation of the second comparison value into the given formatter.
-    This function is especially useful if a [`Comparison`] is used as a trait
-    object [`ComparisonErrorTrait`] in order to erase the underlying type.
+    Returns the [`Debug`](std::fmt::Debug) representation of the value at
+    `index` (without the variable name), if it exists.
      */
-    fn fmt_second_val(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn value_debug(&self, index: usize) -> Option<String>;
 
     /**
-    Writes the representation of the third comparison value into the given formatter.
-    This function is especially useful if a [`Comparison`] is used as a trait
-    object [`ComparisonErrorTrait`] in order to erase the underlying type.
+    Writes the representation of the comparison value at `index` into the given
+    formatter. This function is especially useful if a [`Comparison`] is used as
+    a trait object [`ComparisonErrorTrait`] in order to erase the underlying
+    type.
 
-    If no third value exists, this function returns an error.
+    If no value exists at `index`, this function returns an error.
+     */
+    fn fmt_val(&self, index: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+
+    /**
+    Returns the comparison operator between the value at `index` and its
+    successor, if it exists.
      */
-    fn fmt_third_val(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result;
+    fn operator(&self, index: usize) -> Option<ComparisonOperator>;
 
     /**
-    Returns the comparison operator between the first and the second value.
+    Returns the location in the source code where the comparison has been
+    created, if it is known.
      */
-    fn comp_first_to_second(&self) -> ComparisonOperator;
+    fn location(&self) -> Option<&'static SourceLocation>;
 
     /**
-    Returns the comparison operator between the second and the third value.
+    Returns the custom message attached to the comparison, if any.
      */
-    fn comp_second_to_third(&self) -> ComparisonOperator;
+    fn message(&self) -> Option<&str>;
+
+    /**
+    Writes the message of the comparison in the given `style`, see
+    [`Comparison::display_with`].
+     */
+    fn fmt_with_style(
+        &self,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result;
+
+    /**
+    Returns an owned snapshot of the comparison, see [`Comparison::to_report`].
+     */
+    fn to_report(&self) -> ComparisonReport;
+}
+
+impl dyn ComparisonErrorTrait {
+    /**
+    Returns true if the underlying type is `T`.
+     */
+    pub fn is<T: ComparisonErrorTrait>(&self) -> bool {
+        let any: &dyn std::any::Any = self;
+        return any.is::<T>();
+    }
+
+    /**
+    Returns a reference to the underlying comparison if it is of type `T`.
+
+    # Examples
+    ```
+    use compare_variables::{compare_variables, Comparison, ComparisonErrorTrait};
+
+    let x = 3.0;
+    let err: Box<dyn ComparisonErrorTrait> = Box::new(compare_variables!(owned x > 10.0).unwrap_err());
+    assert!(!err.is_true());
+    assert_eq!(err.variable_name(0), Some("x"));
+    assert_eq!(err.value_debug(1).as_deref(), Some("10.0"));
+
+    let cmp = err.downcast_ref::<Comparison<f64>>().unwrap();
+    assert_eq!(cmp.first_val().value, 3.0);
+    assert!(err.downcast_ref::<Comparison<i32>>().is_none());
+    ```
+     */
+    pub fn downcast_ref<T: ComparisonErrorTrait>(&self) -> Option<&T> {
+        let any: &dyn std::any::Any = self;
+        return any.downcast_ref::<T>();
+    }
+
+    /**
+    Returns a mutable reference to the underlying comparison if it is of type
+    `T`.
+     */
+    pub fn downcast_mut<T: ComparisonErrorTrait>(&mut self) -> Option<&mut T> {
+        let any: &mut dyn std::any::Any = self;
+        return any.downcast_mut::<T>();
+    }
+
+    /**
+    Converts the boxed trait object into the underlying comparison if it is of
+    type `T` and returns the unchanged box otherwise.
+     */
+    pub fn downcast<T: ComparisonErrorTrait>(self: Box<Self>) -> Result<Box<T>, Box<Self>> {
+        if self.is::<T>() {
+            let any: Box<dyn std::any::Any> = self;
+            return Ok(any
+                .downcast::<T>()
+                .expect("the type has been checked before"));
+        } else {
+            return Err(self);
+        }
+    }
+
+    /**
+    Returns an object which displays the comparison using the given `style`
+    instead of the [default style](default_style).
+
+    # Examples
+    ```
+    use compare_variables::{compare_variables, ComparisonErrorTrait, German, LocalizedStyle};
+
+    let x = 3;
+    let err: Box<dyn ComparisonErrorTrait> = Box::new(compare_variables!(owned x > 10).unwrap_err());
+    assert_eq!(
+        err.display_with(&LocalizedStyle::new(&German)).to_string(),
+        "x muss größer als 10 sein, war aber 3"
+    );
+    ```
+     */
+    pub fn display_with<'a>(&'a self, style: &'a dyn MessageStyle) -> impl std::fmt::Display + 'a {
+        return StyledError { error: self, style };
+    }
 }
 
-impl<T: PartialOrd + std::fmt::Debug + Sync + Send + 'static> ComparisonErrorTrait
-    for Comparison<T>
+/// A [`ComparisonErrorTrait`] object which is displayed in a specific [`MessageStyle`].
+struct StyledError<'a> {
+    error: &'a dyn ComparisonErrorTrait,
+    style: &'a dyn MessageStyle,
+}
+
+impl std::fmt::Display for StyledError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return self.error.fmt_with_style(self.style, f);
+    }
+}
+
+impl<L, R> ComparisonErrorTrait for Comparison<L, R>
+where
+    L: PartialOrd<R> + std::fmt::Debug + Sync + Send + 'static,
+    R: std::fmt::Debug + Sync + Send + 'static,
 {
-    fn fmt_first_val(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return std::fmt::Display::fmt(self.first_val(), f);
+    fn len(&self) -> usize {
+        return Comparison::len(self);
+    }
+
+    fn outcome(&self) -> ComparisonOutcome {
+        return Comparison::outcome(self);
+    }
+
+    fn variable_name(&self, index: usize) -> Option<&str> {
+        return style::ErasedComparison::variable_name(self, index);
     }
 
-    fn fmt_second_val(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        return std::fmt::Display::fmt(self.second_val(), f);
+    fn value_debug(&self, index: usize) -> Option<String> {
+        return style::ErasedComparison::value(self, index).map(|value| format!("{value:?}"));
     }
 
-    fn fmt_third_val(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self.third_val() {
-            Some(v) => return std::fmt::Display::fmt(v, f),
-            None => return Err(std::fmt::Error),
+    fn fmt_val(&self, index: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if index < self.len() {
+            return self.display_val(index).fmt(f);
+        } else {
+            return Err(std::fmt::Error);
         }
     }
 
-    fn comp_first_to_second(&self) -> ComparisonOperator {
-        return self.comp_first_to_second;
+    fn operator(&self, index: usize) -> Option<ComparisonOperator> {
+        return Comparison::operator(self, index);
+    }
+
+    fn location(&self) -> Option<&'static SourceLocation> {
+        return Comparison::location(self);
+    }
+
+    fn message(&self) -> Option<&str> {
+        return Comparison::message(self);
+    }
+
+    fn fmt_with_style(
+        &self,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        return Comparison::fmt_with_style(self, style, f);
     }
 
-    fn comp_second_to_third(&self) -> ComparisonOperator {
-        return self.comp_second_to_third;
+    fn to_report(&self) -> ComparisonReport {
+        return Comparison::to_report(self);
     }
 }
@@ -0,0 +1,447 @@
+/*!
+Message catalogs for rendering [`Comparison`](crate::Comparison)s in different
+languages, see [`MessageCatalog`].
+ */
+
+use std::collections::HashMap;
+use std::fmt::Formatter;
+use std::path::Path;
+
+use crate::{ComparisonOperator, ComparisonOutcome};
+
+/**
+All keys which are used by [`LocalizedStyle`](crate::LocalizedStyle) and the
+header of [`ComparisonSet`](crate::ComparisonSet) together with their English
+phrases.
+
+The phrases are templates where arguments in curly braces are replaced when
+rendering a message (literal braces are written as `{{` and `}}`):
+- `operator.*`: The relation to the `{reference}` value, e.g. "greater than
+  {reference}".
+- `tolerance.*`: The `{limit}` of an approximate comparison.
+- `between`: The relation of a value to the `{lower}` and `{upper}` bound.
+- `bound.*`: Marks a bound as inclusive or exclusive.
+- `verdict.*`: The entire statement, built from the `{subject}` (variable
+  name), its `{value}` and the `{relation}` (e.g. "greater than 10"). The keys
+  ending with `.unnamed` are used for values without a variable name. The keys
+  `verdict.trivial*` describe a comparison without links, which has no
+  `{relation}`.
+- `separator`: Separates the statements about multiple failed links.
+- `set.*`: The header of a [`ComparisonSet`](crate::ComparisonSet) with the
+  number of `{failed}` comparisons out of the `{total}` comparisons.
+- `condition.*`: The boolean operands of a [`Condition`](crate::Condition)
+  with the source code of their `{expression}` and the number (`{count}`) of
+  operands which have not been evaluated.
+ */
+pub const CATALOG_KEYS: &[(&str, &str)] = &[
+    ("operator.lesser", "less than {reference}"),
+    (
+        "operator.lesser_or_equal",
+        "less than or equal to {reference}",
+    ),
+    ("operator.equal", "equal to {reference}"),
+    ("operator.inequal", "different from {reference}"),
+    (
+        "operator.greater_or_equal",
+        "greater than or equal to {reference}",
+    ),
+    ("operator.greater", "greater than {reference}"),
+    (
+        "operator.approx_equal",
+        "approximately equal to {reference}",
+    ),
+    ("tolerance.absolute", "absolute tolerance: {limit}"),
+    ("tolerance.relative", "relative tolerance: {limit}"),
+    ("tolerance.ulps", "tolerance: {limit} ULPs"),
+    ("between", "between {lower} and {upper}"),
+    ("bound.inclusive", "inclusive"),
+    ("bound.exclusive", "exclusive"),
+    ("verdict.true", "{subject} is {value}, which is {relation}"),
+    (
+        "verdict.false",
+        "{subject} must be {relation}, but was {value}",
+    ),
+    (
+        "verdict.incomparable",
+        "{subject} must be {relation}, but was {value} (values are not comparable)",
+    ),
+    ("verdict.true.unnamed", "{value} is {relation}"),
+    ("verdict.false.unnamed", "{value} is not {relation}"),
+    (
+        "verdict.incomparable.unnamed",
+        "{value} is not {relation} (values are not comparable)",
+    ),
+    (
+        "verdict.trivial",
+        "{subject} is {value}, which is not compared to anything",
+    ),
+    (
+        "verdict.trivial.unnamed",
+        "{value} is not compared to anything",
+    ),
+    ("separator", "; "),
+    ("set.passed", "all {total} comparisons passed"),
+    ("set.failed", "{failed} of {total} comparisons failed:"),
+    ("condition.true", "`{expression}` is true"),
+    ("condition.false", "`{expression}` is false"),
+    ("condition.skipped.one", "(1 operand not evaluated)"),
+    (
+        "condition.skipped.other",
+        "({count} operands not evaluated)",
+    ),
+];
+
+const GERMAN: &[(&str, &str)] = &[
+    ("operator.lesser", "kleiner als {reference}"),
+    (
+        "operator.lesser_or_equal",
+        "kleiner als oder gleich {reference}",
+    ),
+    ("operator.equal", "gleich {reference}"),
+    ("operator.inequal", "ungleich {reference}"),
+    (
+        "operator.greater_or_equal",
+        "größer als oder gleich {reference}",
+    ),
+    ("operator.greater", "größer als {reference}"),
+    ("operator.approx_equal", "ungefähr gleich {reference}"),
+    ("tolerance.absolute", "absolute Toleranz: {limit}"),
+    ("tolerance.relative", "relative Toleranz: {limit}"),
+    ("tolerance.ulps", "Toleranz: {limit} ULPs"),
+    ("between", "zwischen {lower} und {upper}"),
+    ("bound.inclusive", "inklusive"),
+    ("bound.exclusive", "exklusive"),
+    ("verdict.true", "{subject} ist {value} und damit {relation}"),
+    (
+        "verdict.false",
+        "{subject} muss {relation} sein, war aber {value}",
+    ),
+    (
+        "verdict.incomparable",
+        "{subject} muss {relation} sein, war aber {value} (Werte sind nicht vergleichbar)",
+    ),
+    ("verdict.true.unnamed", "{value} ist {relation}"),
+    ("verdict.false.unnamed", "{value} ist nicht {relation}"),
+    (
+        "verdict.incomparable.unnamed",
+        "{value} ist nicht {relation} (Werte sind nicht vergleichbar)",
+    ),
+    (
+        "verdict.trivial",
+        "{subject} ist {value} und wird mit nichts verglichen",
+    ),
+    (
+        "verdict.trivial.unnamed",
+        "{value} wird mit nichts verglichen",
+    ),
+    ("separator", "; "),
+    ("set.passed", "alle {total} Vergleiche bestanden"),
+    (
+        "set.failed",
+        "{failed} von {total} Vergleichen fehlgeschlagen:",
+    ),
+    ("condition.true", "`{expression}` ist wahr"),
+    ("condition.false", "`{expression}` ist falsch"),
+    ("condition.skipped.one", "(1 Operand nicht ausgewertet)"),
+    (
+        "condition.skipped.other",
+        "({count} Operanden nicht ausgewertet)",
+    ),
+];
+
+fn lookup_table(table: &'static [(&'static str, &'static str)], key: &str) -> Option<&'static str> {
+    return table
+        .iter()
+        .find(|(candidate, _)| *candidate == key)
+        .map(|(_, phrase)| *phrase);
+}
+
+/**
+Translates the phrases used by [`LocalizedStyle`](crate::LocalizedStyle) into
+a language.
+
+Only [`MessageCatalog::lookup`] needs to be implemented. Phrases which are not
+available in the catalog fall back to [`English`]. See [`CATALOG_KEYS`] for all
+keys and their meaning.
+
+Only the messages of [`LocalizedStyle`](crate::LocalizedStyle) (and therefore
+[`NaturalLanguageStyle`](crate::NaturalLanguageStyle)) and the phrases written
+around them by [`ComparisonSet`](crate::ComparisonSet) and
+[`Condition`](crate::Condition) are localizable. The
+[`ExpressionStyle`](crate::ExpressionStyle) writes comparisons as Rust
+expressions with fixed English annotations (e.g. "is false", "failed:" and the
+deviation of approximate comparisons) and does not use a catalog.
+
+# Examples
+```
+use compare_variables::{compare_variables, LocalizedStyle, MessageCatalog};
+
+struct Pirate;
+
+impl MessageCatalog for Pirate {
+    fn lookup(&self, key: &str) -> Option<&str> {
+        match key {
+            "verdict.false" => Some("arr, {subject} be {value}, not {relation}"),
+            _ => None,
+        }
+    }
+}
+
+let x = 3;
+let err = compare_variables!(x > 10).unwrap_err();
+assert_eq!(
+    err.display_with(&LocalizedStyle::new(&Pirate)).to_string(),
+    "arr, x be 3, not greater than 10"
+);
+```
+ */
+pub trait MessageCatalog: Send + Sync {
+    /**
+    Returns the phrase for the given `key`, if the catalog contains it.
+     */
+    fn lookup(&self, key: &str) -> Option<&str>;
+
+    /**
+    Returns the phrase for the given `key`, falling back to [`English`] if the
+    catalog does not contain it. Unknown keys return an empty string.
+     */
+    fn phrase(&self, key: &str) -> &str {
+        return self
+            .lookup(key)
+            .unwrap_or_else(|| English.lookup(key).unwrap_or_default());
+    }
+
+    /**
+    Returns the phrase describing the relation defined by `operator`, e.g.
+    "greater than {reference}".
+     */
+    fn operator(&self, operator: ComparisonOperator) -> &str {
+        let key = match operator {
+            ComparisonOperator::Lesser => "operator.lesser",
+            ComparisonOperator::LesserOrEqual => "operator.lesser_or_equal",
+            ComparisonOperator::Equal => "operator.equal",
+            ComparisonOperator::Inequal => "operator.inequal",
+            ComparisonOperator::GreaterOrEqual => "operator.greater_or_equal",
+            ComparisonOperator::Greater => "operator.greater",
+        };
+        return self.phrase(key);
+    }
+
+    /**
+    Returns the template of the statement for the given `outcome`, depending on
+    whether the subject of the statement has a variable name (`named`).
+     */
+    fn verdict(&self, outcome: ComparisonOutcome, named: bool) -> &str {
+        let key = match (outcome, named) {
+            (ComparisonOutcome::True, true) => "verdict.true",
+            (ComparisonOutcome::False, true) => "verdict.false",
+            (ComparisonOutcome::Incomparable, true) => "verdict.incomparable",
+            (ComparisonOutcome::True, false) => "verdict.true.unnamed",
+            (ComparisonOutcome::False, false) => "verdict.false.unnamed",
+            (ComparisonOutcome::Incomparable, false) => "verdict.incomparable.unnamed",
+        };
+        return self.phrase(key);
+    }
+}
+
+/**
+The bundled English [`MessageCatalog`], which is used by
+[`NaturalLanguageStyle`](crate::NaturalLanguageStyle).
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct English;
+
+impl MessageCatalog for English {
+    fn lookup(&self, key: &str) -> Option<&str> {
+        return lookup_table(CATALOG_KEYS, key);
+    }
+}
+
+/**
+The bundled German [`MessageCatalog`].
+
+# Examples
+```
+use compare_variables::{compare_variables, German, LocalizedStyle};
+
+let x = 2;
+let err = compare_variables!(x in 0..=1).unwrap_err();
+assert_eq!(
+    err.display_with(&LocalizedStyle::new(&German)).to_string(),
+    "x muss zwischen 0 und 1 (inklusive) sein, war aber 2"
+);
+```
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct German;
+
+impl MessageCatalog for German {
+    fn lookup(&self, key: &str) -> Option<&str> {
+        return lookup_table(GERMAN, key);
+    }
+}
+
+/**
+Returns the bundled [`MessageCatalog`] for the given language tag (e.g. `"de"`
+or `"de-AT"`), if one exists. Only the primary language subtag is taken into
+account.
+
+```
+use compare_variables::bundled_catalog;
+
+assert!(bundled_catalog("en-US").is_some());
+assert!(bundled_catalog("de_DE").is_some());
+assert!(bundled_catalog("ja").is_none());
+```
+ */
+pub fn bundled_catalog(language: &str) -> Option<&'static dyn MessageCatalog> {
+    let primary = language.split(['-', '_']).next().unwrap_or_default();
+    if primary.eq_ignore_ascii_case("en") {
+        return Some(&English);
+    } else if primary.eq_ignore_ascii_case("de") {
+        return Some(&German);
+    } else {
+        return None;
+    }
+}
+
+/**
+A [`MessageCatalog`] which is loaded at runtime from a simple text format.
+
+Each line contains a key (see [`CATALOG_KEYS`]) and the corresponding phrase,
+separated by `=`. Surrounding whitespace is removed from keys and phrases,
+unless the phrase is enclosed in double quotes. Empty lines and lines starting
+with `#` are ignored. Keys which are not contained in the file fall back to
+[`English`].
+
+# Examples
+```
+use compare_variables::{compare_variables, Catalog, LocalizedStyle};
+
+let catalog = Catalog::parse(r#"
+## French
+operator.greater = supérieur à {reference}
+verdict.false = {subject} doit être {relation}, mais valait {value}
+"#).unwrap();
+
+let x = 3;
+let err = compare_variables!(x > 10).unwrap_err();
+assert_eq!(
+    err.display_with(&LocalizedStyle::new(&catalog)).to_string(),
+    "x doit être supérieur à 10, mais valait 3"
+);
+```
+ */
+#[derive(Debug, Clone, Default)]
+pub struct Catalog {
+    phrases: HashMap<String, String>,
+}
+
+impl Catalog {
+    /**
+    Parses a catalog from the given `text`.
+     */
+    pub fn parse(text: &str) -> Result<Self, CatalogError> {
+        let mut phrases = HashMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let Some((key, phrase)) = line.split_once('=') else {
+                return Err(CatalogError::Syntax { line: index + 1 });
+            };
+            let key = key.trim();
+            if lookup_table(CATALOG_KEYS, key).is_none() {
+                return Err(CatalogError::UnknownKey {
+                    line: index + 1,
+                    key: key.to_string(),
+                });
+            }
+            let phrase = phrase.trim();
+            let phrase = phrase
+                .strip_prefix('"')
+                .and_then(|phrase| phrase.strip_suffix('"'))
+                .unwrap_or(phrase);
+            phrases.insert(key.to_string(), phrase.to_string());
+        }
+        return Ok(Self { phrases });
+    }
+
+    /**
+    Reads and parses a catalog from the file at `path`.
+     */
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, CatalogError> {
+        let text = std::fs::read_to_string(path).map_err(CatalogError::Io)?;
+        return Self::parse(&text);
+    }
+}
+
+impl MessageCatalog for Catalog {
+    fn lookup(&self, key: &str) -> Option<&str> {
+        return self.phrases.get(key).map(String::as_str);
+    }
+}
+
+/**
+The error returned when loading a [`Catalog`] fails.
+ */
+#[derive(Debug)]
+pub enum CatalogError {
+    /// The file could not be read.
+    Io(std::io::Error),
+    /// The line does not have the format `key = phrase`.
+    Syntax { line: usize },
+    /// The key on the line is not contained in [`CATALOG_KEYS`].
+    UnknownKey { line: usize, key: String },
+}
+
+impl std::fmt::Display for CatalogError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CatalogError::Io(error) => write!(f, "could not read catalog: {error}"),
+            CatalogError::Syntax { line } => {
+                write!(f, "line {line}: expected the format `key = phrase`")
+            }
+            CatalogError::UnknownKey { line, key } => {
+                write!(f, "line {line}: unknown key \"{key}\"")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CatalogError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CatalogError::Io(error) => return Some(error),
+            _ => return None,
+        }
+    }
+}
+
+/**
+Writes the `template` into the formatter, replacing each argument in curly
+braces via `argument`. `{{` and `}}` are written as literal braces.
+ */
+pub(crate) fn write_template(
+    f: &mut Formatter<'_>,
+    template: &str,
+    argument: &mut dyn FnMut(&str, &mut Formatter<'_>) -> std::fmt::Result,
+) -> std::fmt::Result {
+    let mut rest = template;
+    while let Some(position) = rest.find(['{', '}']) {
+        f.write_str(&rest[..position])?;
+        let tail = &rest[position..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            f.write_str(&tail[..1])?;
+            rest = &tail[2..];
+        } else if let (true, Some(end)) = (tail.starts_with('{'), tail.find('}')) {
+            argument(&tail[1..end], f)?;
+            rest = &tail[end + 1..];
+        } else {
+            f.write_str(&tail[..1])?;
+            rest = &tail[1..];
+        }
+    }
+    return f.write_str(rest);
+}
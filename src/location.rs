@@ -0,0 +1,60 @@
+/*!
+Location in the source code where a [`Comparison`](crate::Comparison) has been
+created, see [`SourceLocation`].
+ */
+
+/**
+The location in the source code where a [`Comparison`](crate::Comparison) has
+been created.
+
+The macro [`compare_variables`](crate::compare_variables) attaches the location
+of its invocation to the created comparison via
+[`Comparison::with_location`](crate::Comparison::with_location). The location
+is then available via [`Comparison::location`](crate::Comparison::location)
+and appended to the message when using the alternate format `{:#}`.
+
+# Examples
+```
+use compare_variables::SourceLocation;
+
+const LOCATION: SourceLocation = SourceLocation::new("src/main.rs", 10, 5, "my_crate::config");
+assert_eq!(LOCATION.to_string(), "src/main.rs:10:5");
+```
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SourceLocation {
+    /// The file as returned by [`file!`].
+    pub file: &'static str,
+    /// The line as returned by [`line!`].
+    pub line: u32,
+    /// The column as returned by [`column!`].
+    pub column: u32,
+    /// The module path as returned by [`module_path!`].
+    pub module_path: &'static str,
+}
+
+impl SourceLocation {
+    /**
+    Returns a new instance of [`SourceLocation`].
+     */
+    pub const fn new(
+        file: &'static str,
+        line: u32,
+        column: u32,
+        module_path: &'static str,
+    ) -> Self {
+        return Self {
+            file,
+            line,
+            column,
+            module_path,
+        };
+    }
+}
+
+impl std::fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
@@ -0,0 +1,498 @@
+/*!
+Logical combinations of comparisons, see [`Condition`].
+ */
+
+use crate::locale::write_template;
+use crate::{
+    Comparison, ComparisonOutcome, ComparisonReport, ComparisonView, MessageStyle, SourceLocation,
+    default_style,
+};
+
+/// Object-safe subset of the [`Comparison`] methods needed by a [`Condition`].
+trait Leaf {
+    fn view(&self) -> ComparisonView<'_>;
+    fn location(&self) -> Option<&'static SourceLocation>;
+    fn message(&self) -> Option<&str>;
+    fn to_report(&self) -> ComparisonReport;
+    fn fmt_with_style(
+        &self,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result;
+}
+
+impl<L, R> Leaf for Comparison<L, R>
+where
+    L: PartialOrd<R> + std::fmt::Debug,
+    R: std::fmt::Debug,
+{
+    fn view(&self) -> ComparisonView<'_> {
+        return Comparison::view(self);
+    }
+
+    fn location(&self) -> Option<&'static SourceLocation> {
+        return Comparison::location(self);
+    }
+
+    fn message(&self) -> Option<&str> {
+        return Comparison::message(self);
+    }
+
+    fn to_report(&self) -> ComparisonReport {
+        return Comparison::to_report(self);
+    }
+
+    fn fmt_with_style(
+        &self,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        return Comparison::fmt_with_style(self, style, f);
+    }
+}
+
+/**
+A type-erased [`Comparison`] which is a leaf of a [`Condition`].
+
+The compared values may be borrowed for the lifetime `'a`. Use
+[`DynComparison::to_report`] to obtain an owned snapshot.
+ */
+pub struct DynComparison<'a>(Box<dyn Leaf + 'a>);
+
+impl DynComparison<'_> {
+    /**
+    Returns a type-erased view of the comparison, see [`Comparison::view`].
+     */
+    pub fn view(&self) -> ComparisonView<'_> {
+        return self.0.view();
+    }
+
+    /**
+    Returns the outcome of the comparison, see [`Comparison::outcome`].
+     */
+    pub fn outcome(&self) -> ComparisonOutcome {
+        return self.0.view().outcome();
+    }
+
+    /**
+    Returns the source location of the comparison, see [`Comparison::location`].
+     */
+    pub fn location(&self) -> Option<&'static SourceLocation> {
+        return self.0.location();
+    }
+
+    /**
+    Returns the custom message of the comparison, see [`Comparison::message`].
+     */
+    pub fn message(&self) -> Option<&str> {
+        return self.0.message();
+    }
+
+    /**
+    Returns a [`ComparisonReport`] of the comparison, see
+    [`Comparison::to_report`].
+     */
+    pub fn to_report(&self) -> ComparisonReport {
+        return self.0.to_report();
+    }
+}
+
+impl std::fmt::Display for DynComparison<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return self.0.fmt_with_style(default_style(), f);
+    }
+}
+
+impl std::fmt::Debug for DynComparison<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return std::fmt::Display::fmt(self, f);
+    }
+}
+
+/**
+The operands of a [`Condition::And`] or [`Condition::Or`].
+
+Operands are evaluated from left to right until the outcome of the junction is
+decided (short-circuit evaluation). Only the evaluated operands are stored, the
+number of remaining operands is available via [`Junction::skipped`].
+ */
+#[derive(Debug)]
+pub struct Junction<'a> {
+    operands: Vec<Condition<'a>>,
+    skipped: usize,
+}
+
+impl<'a> Junction<'a> {
+    /**
+    Returns the evaluated operands.
+     */
+    pub fn operands(&self) -> &[Condition<'a>] {
+        return &self.operands;
+    }
+
+    /**
+    Returns the number of operands which have not been evaluated because the
+    outcome was already decided.
+     */
+    pub fn skipped(&self) -> usize {
+        return self.skipped;
+    }
+
+    fn to_report(&self) -> Junction<'static> {
+        return Junction {
+            operands: self.operands.iter().map(Condition::to_report).collect(),
+            skipped: self.skipped,
+        };
+    }
+
+    fn fmt_with_style(
+        &self,
+        symbol: &str,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        for (index, operand) in self.operands.iter().enumerate() {
+            if index > 0 {
+                write!(f, " {symbol} ")?;
+            }
+            // Nested junctions are put in parentheses
+            if let Condition::And(_) | Condition::Or(_) = operand {
+                write!(f, "(")?;
+                operand.fmt_with_style(style, f)?;
+                write!(f, ")")?;
+            } else {
+                operand.fmt_with_style(style, f)?;
+            }
+        }
+        let key = match self.skipped {
+            0 => return Ok(()),
+            1 => "condition.skipped.one",
+            _ => "condition.skipped.other",
+        };
+        write!(f, " {symbol} ")?;
+        return write_template(f, style.catalog().phrase(key), &mut |argument, f| {
+            if argument == "count" {
+                write!(f, "{}", self.skipped)?;
+            }
+            return Ok(());
+        });
+    }
+}
+
+/**
+A logical combination of comparisons and boolean values.
+
+A condition is usually created by the macro
+[`compare_variables`](crate::compare_variables) when comparisons are combined
+with the logical operators `&&`, `||` and `!`. Like in Rust, `&&` binds more
+tightly than `||`, `!` is applied to the following parenthesized group and
+the right-hand side of `&&` and `||` is only evaluated if it is needed to decide
+the outcome. Operands which are not comparisons (e.g. `allow_zero`) must
+evaluate to a `bool`.
+
+The [`Display`](std::fmt::Display) implementation shows the message of each
+evaluated comparison, the value of each boolean operand and the number of
+operands which have not been evaluated, so it is obvious which branch failed.
+
+The compared values do not need to be [`Send`] or [`Sync`], therefore a
+condition is neither of them. To pass a failed condition to another thread
+(e.g. as `Box<dyn Error + Send + Sync>`), convert it into a string first.
+
+# Outcome
+
+- A comparison has its own [`Comparison::outcome`], a boolean is either true or
+  false.
+- [`Condition::Not`] swaps true and false. Incomparable values (e.g. NaN) stay
+  incomparable, so `!(x < y)` is not true if `x` is NaN.
+- [`Condition::And`] is true if all operands are true. Otherwise, it has the
+  outcome of its first operand which is not true.
+- [`Condition::Or`] is true if any operand is true. Otherwise, it is
+  incomparable if any operand is incomparable and false if not.
+
+# Examples
+```
+use compare_variables::{compare_variables, ComparisonOutcome};
+
+let (x, allow_zero) = (0, false);
+assert!(compare_variables!(x > 0 || x == 0).is_ok());
+
+let err = compare_variables!(x > 0 || allow_zero).unwrap_err();
+assert_eq!(err.outcome(), ComparisonOutcome::False);
+assert_eq!(err.to_string(), "`x (value: 0) > 0` is false || `allow_zero` is false");
+
+let (a, b, c) = (1.0, 0.5, 2.0);
+let err = compare_variables!((a < b) && (b < c) || !(a ~= c, abs = 1.0)).unwrap_err();
+assert_eq!(
+    err.to_string(),
+    "(`a (value: 1.0) < b (value: 0.5)` is false && (1 operand not evaluated)) || \
+     !(`a (value: 1.0) ~= c (value: 2.0)` is true (absolute deviation: 1.0, tolerance: 1.0))"
+);
+```
+ */
+pub enum Condition<'a> {
+    /// A comparison.
+    Comparison(DynComparison<'a>),
+    /// A boolean value and the expression it has been created from.
+    Bool {
+        /// The value of the expression.
+        value: bool,
+        /// The source code of the expression.
+        expression: &'static str,
+    },
+    /// The negation of a condition.
+    Not(Box<Condition<'a>>),
+    /// A condition which is true if all operands are true.
+    And(Junction<'a>),
+    /// A condition which is true if any operand is true.
+    Or(Junction<'a>),
+}
+
+impl<'a> Condition<'a> {
+    /**
+    Returns a new condition from a boolean value and the expression it has been
+    created from.
+
+    ```
+    use compare_variables::Condition;
+
+    let allow_zero = true;
+    let condition = Condition::from_bool(allow_zero, "allow_zero");
+    assert!(condition.is_true());
+    assert_eq!(condition.to_string(), "`allow_zero` is true");
+    ```
+     */
+    pub fn from_bool(value: bool, expression: &'static str) -> Self {
+        return Condition::Bool { value, expression };
+    }
+
+    /**
+    Combines the condition with the condition returned by `other` via a logical
+    and. The function `other` is only called if this condition is true.
+    Chaining calls of this function results in a single [`Condition::And`].
+
+    ```
+    use compare_variables::{Comparison, ComparisonOperator, ComparisonValue, Condition};
+
+    let x = 5;
+    let condition = Condition::from(Comparison::new(
+        ComparisonValue::new(x, Some("x")),
+        [(ComparisonOperator::Greater, ComparisonValue::new(0, None))],
+    ))
+    .and(|| Condition::from_bool(x % 2 == 0, "x % 2 == 0"));
+    assert_eq!(condition.to_string(), "`x (value: 5) > 0` is true && `x % 2 == 0` is false");
+    ```
+     */
+    pub fn and(self, other: impl FnOnce() -> Condition<'a>) -> Self {
+        let mut junction = match self {
+            Condition::And(junction) => junction,
+            condition => Junction {
+                operands: vec![condition],
+                skipped: 0,
+            },
+        };
+        if junction.skipped == 0 && junction.operands.iter().all(Condition::is_true) {
+            junction.operands.push(other());
+        } else {
+            junction.skipped += 1;
+        }
+        return Condition::And(junction);
+    }
+
+    /**
+    Combines the condition with the condition returned by `other` via a logical
+    or. The function `other` is only called if this condition is not true.
+    Chaining calls of this function results in a single [`Condition::Or`].
+     */
+    pub fn or(self, other: impl FnOnce() -> Condition<'a>) -> Self {
+        let mut junction = match self {
+            Condition::Or(junction) => junction,
+            condition => Junction {
+                operands: vec![condition],
+                skipped: 0,
+            },
+        };
+        if junction.skipped == 0 && !junction.operands.iter().any(Condition::is_true) {
+            junction.operands.push(other());
+        } else {
+            junction.skipped += 1;
+        }
+        return Condition::Or(junction);
+    }
+
+    /**
+    Returns the outcome of the condition, see the
+    [type documentation](Condition#outcome).
+     */
+    pub fn outcome(&self) -> ComparisonOutcome {
+        match self {
+            Condition::Comparison(comparison) => return comparison.outcome(),
+            Condition::Bool { value: true, .. } => return ComparisonOutcome::True,
+            Condition::Bool { value: false, .. } => return ComparisonOutcome::False,
+            Condition::Not(condition) => match condition.outcome() {
+                ComparisonOutcome::True => return ComparisonOutcome::False,
+                ComparisonOutcome::False => return ComparisonOutcome::True,
+                ComparisonOutcome::Incomparable => return ComparisonOutcome::Incomparable,
+            },
+            Condition::And(junction) => {
+                for operand in junction.operands.iter() {
+                    let outcome = operand.outcome();
+                    if outcome != ComparisonOutcome::True {
+                        return outcome;
+                    }
+                }
+                return ComparisonOutcome::True;
+            }
+            Condition::Or(junction) => {
+                let mut outcome = ComparisonOutcome::False;
+                for operand in junction.operands.iter() {
+                    match operand.outcome() {
+                        ComparisonOutcome::True => return ComparisonOutcome::True,
+                        ComparisonOutcome::Incomparable => {
+                            outcome = ComparisonOutcome::Incomparable
+                        }
+                        ComparisonOutcome::False => (),
+                    }
+                }
+                return outcome;
+            }
+        }
+    }
+
+    /**
+    Returns whether the condition evaluates to true.
+     */
+    pub fn is_true(&self) -> bool {
+        return self.outcome() == ComparisonOutcome::True;
+    }
+
+    /**
+    Wraps the condition in [`Ok`] if [`Condition::is_true`] is true and in
+    [`Err`] otherwise.
+     */
+    pub fn into_result(self) -> Result<Self, Self> {
+        if self.is_true() {
+            return Ok(self);
+        } else {
+            return Err(self);
+        }
+    }
+
+    /**
+    Returns an owned snapshot of the condition where all comparisons have been
+    converted into [`ComparisonReport`]s.
+
+    ```
+    use compare_variables::{compare_variables, Condition};
+
+    fn check(x: &str, empty_allowed: bool) -> Result<(), Condition<'static>> {
+        compare_variables!(x.len() > 0 || empty_allowed).map_err(|c| c.to_report())?;
+        return Ok(());
+    }
+    assert_eq!(
+        check("", false).unwrap_err().to_string(),
+        "`x.len() (value: 0) > 0` is false || `empty_allowed` is false"
+    );
+    ```
+     */
+    pub fn to_report(&self) -> Condition<'static> {
+        match self {
+            Condition::Comparison(comparison) => {
+                return Condition::from(comparison.to_report());
+            }
+            Condition::Bool { value, expression } => {
+                return Condition::Bool {
+                    value: *value,
+                    expression,
+                };
+            }
+            Condition::Not(condition) => return Condition::Not(Box::new(condition.to_report())),
+            Condition::And(junction) => return Condition::And(junction.to_report()),
+            Condition::Or(junction) => return Condition::Or(junction.to_report()),
+        }
+    }
+
+    /**
+    Returns an object which displays the condition using the given `style`
+    for all comparisons instead of the [default style](default_style).
+     */
+    pub fn display_with<'b>(&'b self, style: &'b dyn MessageStyle) -> impl std::fmt::Display + 'b {
+        return StyledCondition {
+            condition: self,
+            style,
+        };
+    }
+
+    fn fmt_with_style(
+        &self,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Condition::Comparison(comparison) => return comparison.0.fmt_with_style(style, f),
+            Condition::Bool { value, expression } => {
+                let key = if *value {
+                    "condition.true"
+                } else {
+                    "condition.false"
+                };
+                return write_template(f, style.catalog().phrase(key), &mut |argument, f| {
+                    if argument == "expression" {
+                        f.write_str(expression)?;
+                    }
+                    return Ok(());
+                });
+            }
+            Condition::Not(condition) => {
+                write!(f, "!(")?;
+                condition.fmt_with_style(style, f)?;
+                return write!(f, ")");
+            }
+            Condition::And(junction) => return junction.fmt_with_style("&&", style, f),
+            Condition::Or(junction) => return junction.fmt_with_style("||", style, f),
+        }
+    }
+}
+
+impl<'a, L, R> From<Comparison<L, R>> for Condition<'a>
+where
+    L: PartialOrd<R> + std::fmt::Debug + 'a,
+    R: std::fmt::Debug + 'a,
+{
+    fn from(value: Comparison<L, R>) -> Self {
+        return Condition::Comparison(DynComparison(Box::new(value)));
+    }
+}
+
+impl std::ops::Not for Condition<'_> {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        return Condition::Not(Box::new(self));
+    }
+}
+
+impl std::fmt::Display for Condition<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return self.fmt_with_style(default_style(), f);
+    }
+}
+
+impl std::fmt::Debug for Condition<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return std::fmt::Display::fmt(self, f);
+    }
+}
+
+impl std::error::Error for Condition<'_> {}
+
+/// A [`Condition`] which is displayed in a specific [`MessageStyle`].
+struct StyledCondition<'a> {
+    condition: &'a Condition<'a>,
+    style: &'a dyn MessageStyle,
+}
+
+impl std::fmt::Display for StyledCondition<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return self.condition.fmt_with_style(self.style, f);
+    }
+}
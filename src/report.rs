@@ -0,0 +1,114 @@
+/*!
+Owned, type-erased snapshots of comparisons, see [`ComparisonReport`].
+ */
+
+use crate::{Comparison, ComparisonValue};
+
+/**
+A value of a [`ComparisonReport`], rendered via its [`Debug`](std::fmt::Debug)
+implementation.
+
+The [`Debug`](std::fmt::Debug) implementation of this type writes the rendered
+string verbatim, so a report is displayed exactly like the comparison it has
+been created from. Rendered values are ordered by comparing the strings.
+ */
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct RenderedValue(String);
+
+impl RenderedValue {
+    /**
+    Returns the rendered value.
+     */
+    pub fn as_str(&self) -> &str {
+        return &self.0;
+    }
+}
+
+impl std::fmt::Debug for RenderedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&self.0);
+    }
+}
+
+impl std::fmt::Display for RenderedValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str(&self.0);
+    }
+}
+
+impl AsRef<str> for RenderedValue {
+    fn as_ref(&self) -> &str {
+        return &self.0;
+    }
+}
+
+/**
+An owned snapshot of a [`Comparison`] where all values have been rendered into
+strings.
+
+Contrary to the [`Comparison`] it has been created from (via
+[`Comparison::to_report`]), a report is `Clone + Send + Sync + 'static`
+regardless of the compared types. This allows storing comparisons of borrowed
+or non-[`Send`] values, e.g. as a `Box<dyn ComparisonErrorTrait>`. The variable
+names, operators, link results, custom message and source location are kept.
+
+# Examples
+```
+use std::rc::Rc;
+use compare_variables::{compare_variables, ComparisonReport};
+
+fn check(x: Rc<i32>) -> Result<(), ComparisonReport> {
+    compare_variables!(x > Rc::new(10)).map_err(|c| c.to_report())?;
+    return Ok(());
+}
+
+// The report can be sent to another thread, although `Rc` is not `Send`
+let err = check(Rc::new(3)).unwrap_err();
+std::thread::spawn(move || {
+    assert_eq!(err.to_string(), "`x (value: 3) > Rc::new(10) (value: 10)` is false");
+})
+.join()
+.unwrap();
+```
+ */
+pub type ComparisonReport = Comparison<RenderedValue>;
+
+fn render<T: std::fmt::Debug>(value: &ComparisonValue<T>) -> ComparisonValue<RenderedValue> {
+    return ComparisonValue {
+        value: RenderedValue(format!("{:?}", value.value)),
+        variable_name: value.variable_name.clone(),
+    };
+}
+
+impl<L: PartialOrd<R> + std::fmt::Debug, R: std::fmt::Debug> Comparison<L, R> {
+    /**
+    Returns a [`ComparisonReport`] of the comparison, which contains all values
+    rendered via their [`Debug`](std::fmt::Debug) implementation.
+
+    ```
+    use compare_variables::{compare_variables, ComparisonReport};
+
+    let (name, other) = (String::from("Bob"), String::from("Alice"));
+    let report: ComparisonReport = compare_variables!(name == other).unwrap_err().to_report();
+    drop((name, other));
+    assert_eq!(
+        report.to_string(),
+        "`name (value: \"Bob\") == other (value: \"Alice\")` is false"
+    );
+    assert_eq!(report.value(0).unwrap().value.as_str(), "\"Bob\"");
+    ```
+     */
+    pub fn to_report(&self) -> ComparisonReport {
+        return Comparison {
+            first_val: render(&self.first_val),
+            other_vals: self.other_vals.iter().map(render).collect(),
+            operators: self.operators.clone(),
+            link_results: self.link_results.clone(),
+            range: self.range.clone(),
+            location: self.location,
+            message: self.message.clone(),
+        };
+    }
+}
@@ -0,0 +1,351 @@
+/*!
+Serialization and deserialization via [serde](https://crates.io/crates/serde),
+which is available with the feature flag `serde`.
+ */
+
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use serde::de::{Error as _, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    Comparison, ComparisonOperator, ComparisonOutcome, ComparisonValue, LinkResult,
+    RangeMembership, SourceLocation, Tolerance,
+};
+
+#[derive(Deserialize)]
+struct ValueData<T> {
+    value: T,
+    variable_name: Option<String>,
+}
+
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for ComparisonValue<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ValueData::<T>::deserialize(deserializer)?;
+        return Ok(ComparisonValue {
+            value: data.value,
+            variable_name: data.variable_name.map(Cow::Owned),
+        });
+    }
+}
+
+/// Returns the operator for the given symbol.
+fn operator_from_symbol(symbol: &str) -> Option<ComparisonOperator> {
+    match symbol {
+        "<" => return Some(ComparisonOperator::Lesser),
+        "<=" => return Some(ComparisonOperator::LesserOrEqual),
+        "==" => return Some(ComparisonOperator::Equal),
+        "!=" => return Some(ComparisonOperator::Inequal),
+        ">=" => return Some(ComparisonOperator::GreaterOrEqual),
+        ">" => return Some(ComparisonOperator::Greater),
+        _ => return None,
+    }
+}
+
+/**
+The operator is serialized as its symbol (see [`ComparisonOperator::as_str`]).
+ */
+impl Serialize for ComparisonOperator {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.serialize_str(self.as_str());
+    }
+}
+
+impl<'de> Deserialize<'de> for ComparisonOperator {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let symbol = String::deserialize(deserializer)?;
+        return operator_from_symbol(&symbol).ok_or_else(|| {
+            D::Error::custom(format!(
+                "invalid comparison operator \"{symbol}\", expected one of <, <=, ==, !=, >=, >"
+            ))
+        });
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OrderingRepr {
+    Less,
+    Equal,
+    Greater,
+}
+
+impl From<std::cmp::Ordering> for OrderingRepr {
+    fn from(value: std::cmp::Ordering) -> Self {
+        match value {
+            std::cmp::Ordering::Less => return OrderingRepr::Less,
+            std::cmp::Ordering::Equal => return OrderingRepr::Equal,
+            std::cmp::Ordering::Greater => return OrderingRepr::Greater,
+        }
+    }
+}
+
+impl From<OrderingRepr> for std::cmp::Ordering {
+    fn from(value: OrderingRepr) -> Self {
+        match value {
+            OrderingRepr::Less => return std::cmp::Ordering::Less,
+            OrderingRepr::Equal => return std::cmp::Ordering::Equal,
+            OrderingRepr::Greater => return std::cmp::Ordering::Greater,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ComparisonRef<'a, L: PartialOrd<R>, R> {
+    values: ValuesRef<'a, L, R>,
+    links: Vec<LinkRef>,
+    outcome: ComparisonOutcome,
+    range: Option<RangeRef<'a>>,
+    message: Option<&'a str>,
+    location: Option<&'a SourceLocation>,
+}
+
+struct ValuesRef<'a, L: PartialOrd<R>, R>(&'a Comparison<L, R>);
+
+impl<L: PartialOrd<R> + Serialize, R: Serialize> Serialize for ValuesRef<'_, L, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        seq.serialize_element(&self.0.first_val)?;
+        for value in self.0.other_vals.iter() {
+            seq.serialize_element(value)?;
+        }
+        return seq.end();
+    }
+}
+
+#[derive(Serialize)]
+struct LinkRef {
+    operator: &'static str,
+    outcome: ComparisonOutcome,
+    ordering: Option<OrderingRepr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tolerance: Option<Tolerance>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    deviation: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct RangeRef<'a> {
+    element: usize,
+    name: Option<&'a str>,
+}
+
+/**
+A comparison is serialized into the following shape (shown as JSON):
+
+```json
+{
+  "values": [
+    {"value": 3, "variable_name": "x"},
+    {"value": 10, "variable_name": null}
+  ],
+  "links": [
+    {"operator": ">", "outcome": "false", "ordering": "less"}
+  ],
+  "outcome": "false",
+  "range": null,
+  "message": null,
+  "location": {"file": "src/main.rs", "line": 4, "column": 5, "module_path": "app"}
+}
+```
+
+- `values`: All values of the chain with their variable names.
+- `links`: The result of comparing each value with its successor via the
+  `operator`. The `outcome` is `"true"`, `"false"` or `"incomparable"`, the
+  `ordering` is `"less"`, `"equal"`, `"greater"` or `null`. Approximate
+  comparisons additionally contain the `tolerance` (e.g. `{"absolute": 0.001}`)
+  and the `deviation`.
+- `outcome`: The verdict of the entire comparison.
+- `range`: For range memberships, the index of the checked value (`element`)
+  and the `name` of the range.
+- `message`: The custom message, see [`Comparison::with_message`].
+- `location`: The source location, see [`Comparison::with_location`].
+
+When deserializing, `outcome` and `location` are ignored, since the former is
+derived from the links and the latter refers to static data. The `outcome` of
+each link is checked against its `ordering` (or its `deviation` and `tolerance`
+for approximate comparisons) and rejected if they contradict each other. The
+orderings and deviations themselves are not recomputed from the values, since
+these may have been rendered into strings (see
+[`ComparisonReport`](crate::ComparisonReport)), therefore a deserialized
+comparison is only as trustworthy as its source.
+ */
+impl<L: PartialOrd<R> + Serialize, R: Serialize> Serialize for Comparison<L, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let links = self
+            .operators
+            .iter()
+            .zip(self.link_results.iter())
+            .map(|(operator, result)| LinkRef {
+                operator: match result.tolerance {
+                    Some(_) => "~=",
+                    None => operator.as_str(),
+                },
+                outcome: result.outcome,
+                ordering: result.ordering.map(OrderingRepr::from),
+                tolerance: result.tolerance,
+                deviation: result.deviation,
+            })
+            .collect();
+        return ComparisonRef {
+            values: ValuesRef(self),
+            links,
+            outcome: self.outcome(),
+            range: self.range.as_ref().map(|range| RangeRef {
+                element: range.element(),
+                name: range.name.as_deref(),
+            }),
+            message: self.message(),
+            location: self.location,
+        }
+        .serialize(serializer);
+    }
+}
+
+#[derive(Deserialize)]
+struct ComparisonData<L, R> {
+    values: ValuesData<L, R>,
+    links: Vec<LinkData>,
+    #[serde(default)]
+    range: Option<RangeData>,
+    #[serde(default)]
+    message: Option<Box<str>>,
+}
+
+struct ValuesData<L, R> {
+    first: ComparisonValue<L>,
+    others: Vec<ComparisonValue<R>>,
+}
+
+impl<'de, L: Deserialize<'de>, R: Deserialize<'de>> Deserialize<'de> for ValuesData<L, R> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return deserializer.deserialize_seq(ValuesVisitor(PhantomData));
+    }
+}
+
+struct ValuesVisitor<L, R>(PhantomData<(L, R)>);
+
+impl<'de, L: Deserialize<'de>, R: Deserialize<'de>> Visitor<'de> for ValuesVisitor<L, R> {
+    type Value = ValuesData<L, R>;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return f.write_str("a non-empty sequence of comparison values");
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let first = seq
+            .next_element()?
+            .ok_or_else(|| A::Error::invalid_length(0, &self))?;
+        let mut others = Vec::new();
+        while let Some(value) = seq.next_element()? {
+            others.push(value);
+        }
+        return Ok(ValuesData { first, others });
+    }
+}
+
+#[derive(Deserialize)]
+struct LinkData {
+    operator: String,
+    outcome: ComparisonOutcome,
+    ordering: Option<OrderingRepr>,
+    #[serde(default)]
+    tolerance: Option<Tolerance>,
+    #[serde(default)]
+    deviation: Option<f64>,
+}
+
+#[derive(Deserialize)]
+struct RangeData {
+    element: usize,
+    #[serde(default)]
+    name: Option<String>,
+}
+
+impl<'de, L, R> Deserialize<'de> for Comparison<L, R>
+where
+    L: PartialOrd<R> + Deserialize<'de>,
+    R: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = ComparisonData::<L, R>::deserialize(deserializer)?;
+        let ValuesData { first, others } = data.values;
+        if data.links.len() != others.len() {
+            return Err(D::Error::custom(format!(
+                "expected {} links for {} values, found {}",
+                others.len(),
+                others.len() + 1,
+                data.links.len()
+            )));
+        }
+
+        let mut operators = Vec::with_capacity(data.links.len());
+        let mut link_results = Vec::with_capacity(data.links.len());
+        for link in data.links {
+            let (operator, tolerance) = match (link.operator.as_str(), link.tolerance) {
+                ("~=", Some(tolerance)) => (ComparisonOperator::Equal, Some(tolerance)),
+                (symbol, None) if symbol != "~=" => match operator_from_symbol(symbol) {
+                    Some(operator) => (operator, None),
+                    None => {
+                        return Err(D::Error::custom(format!(
+                            "invalid comparison operator \"{symbol}\""
+                        )));
+                    }
+                },
+                _ => {
+                    return Err(D::Error::custom(format!(
+                        "link {} has operator \"{}\", but only \"~=\" requires a tolerance",
+                        operators.len(),
+                        link.operator
+                    )));
+                }
+            };
+            let ordering = link.ordering.map(std::cmp::Ordering::from);
+            let outcome = match tolerance {
+                Some(tolerance) => tolerance.outcome(link.deviation),
+                None => operator.outcome(ordering),
+            };
+            if outcome != link.outcome {
+                return Err(D::Error::custom(format!(
+                    "outcome of link {} contradicts its ordering or deviation",
+                    operators.len()
+                )));
+            }
+            operators.push(operator);
+            link_results.push(LinkResult {
+                ordering,
+                outcome,
+                deviation: link.deviation,
+                tolerance,
+            });
+        }
+
+        let range = match data.range {
+            Some(range) if range.element <= usize::from(!others.is_empty()) => {
+                Some(Box::new(RangeMembership {
+                    has_start: range.element == 1,
+                    name: range.name.map(Cow::Owned),
+                }))
+            }
+            Some(range) => {
+                return Err(D::Error::custom(format!(
+                    "invalid index {} of the range element",
+                    range.element
+                )));
+            }
+            None => None,
+        };
+
+        return Ok(Comparison {
+            first_val: first,
+            other_vals: others.into_boxed_slice(),
+            operators: operators.into_boxed_slice(),
+            link_results: link_results.into_boxed_slice(),
+            range,
+            location: None,
+            message: data.message,
+        });
+    }
+}
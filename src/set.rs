@@ -0,0 +1,268 @@
+/*!
+Collecting the results of many comparisons, see [`ComparisonSet`].
+ */
+
+use crate::locale::write_template;
+use crate::{AnyComparison, MessageStyle, default_style};
+
+/**
+A collection of the results of multiple comparisons of possibly different
+types.
+
+Contrary to the `?` operator, which returns on the first comparison which is
+not true, a [`ComparisonSet`] records the outcome of every comparison it is fed
+with. The failed comparisons are stored as [`AnyComparison`]s, while passed
+comparisons are only counted. After all comparisons have been recorded,
+[`ComparisonSet::into_result`] returns the set as an error if any comparison
+failed. The [`Display`](std::fmt::Display) implementation lists all failures;
+the alternate format `{:#}` is forwarded to the individual comparisons.
+
+The macro [`validate`](crate::validate) creates a set from a list of
+comparisons.
+
+# Examples
+```
+use compare_variables::{compare_variables, ComparisonSet};
+
+struct Config {
+    threads: usize,
+    ratio: f64,
+}
+
+fn check(config: &Config) -> Result<(), ComparisonSet> {
+    let mut set = ComparisonSet::new();
+    set.check(compare_variables!(config.threads > 0));
+    set.check(compare_variables!(0.0 <= config.ratio <= 1.0));
+    return set.into_result();
+}
+
+let err = check(&Config { threads: 0, ratio: 1.5 }).unwrap_err();
+assert_eq!(err.passed(), 0);
+assert_eq!(err.failed(), 2);
+assert_eq!(
+    err.to_string(),
+    "2 of 2 comparisons failed:\n\
+     - `config.threads (value: 0) > 0` is false\n\
+     - `0.0 <= config.ratio (value: 1.5) <= 1.0` is false (failed: `config.ratio (value: 1.5) <= 1.0`)"
+);
+```
+ */
+#[derive(Debug, Default)]
+pub struct ComparisonSet {
+    passed: usize,
+    failures: Vec<AnyComparison>,
+}
+
+impl ComparisonSet {
+    /**
+    Returns an empty [`ComparisonSet`].
+     */
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /**
+    Records the result of a comparison (e.g. the output of
+    [`compare_variables`](crate::compare_variables)) and returns whether it
+    passed. The error of a failed comparison is converted into an
+    [`AnyComparison`].
+     */
+    pub fn check<T, E: Into<AnyComparison>>(&mut self, result: Result<T, E>) -> bool {
+        match result {
+            Ok(_) => {
+                self.passed += 1;
+                return true;
+            }
+            Err(error) => {
+                self.failures.push(error.into());
+                return false;
+            }
+        }
+    }
+
+    /**
+    Records a comparison as passed or failed depending on its
+    [`outcome`](crate::ComparisonErrorTrait::outcome) and returns whether it
+    passed.
+
+    ```
+    use compare_variables::{Comparison, ComparisonOperator, ComparisonSet, ComparisonValue};
+
+    let mut set = ComparisonSet::new();
+    for x in [1.0, -1.0, 2.0] {
+        set.push(Comparison::new(
+            ComparisonValue::new(x, Some("x")),
+            [(ComparisonOperator::Greater, ComparisonValue::new(0.0, None))],
+        ));
+    }
+    assert_eq!(set.len(), 3);
+    assert_eq!(set.failed(), 1);
+    ```
+     */
+    pub fn push<C: Into<AnyComparison>>(&mut self, comparison: C) -> bool {
+        let comparison = comparison.into();
+        if comparison.is_true() {
+            self.passed += 1;
+            return true;
+        } else {
+            self.failures.push(comparison);
+            return false;
+        }
+    }
+
+    /**
+    Returns the number of recorded comparisons.
+     */
+    pub fn len(&self) -> usize {
+        return self.passed + self.failures.len();
+    }
+
+    /**
+    Returns true if no comparison has been recorded.
+     */
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /**
+    Returns the number of passed comparisons.
+     */
+    pub fn passed(&self) -> usize {
+        return self.passed;
+    }
+
+    /**
+    Returns the number of failed comparisons.
+     */
+    pub fn failed(&self) -> usize {
+        return self.failures.len();
+    }
+
+    /**
+    Returns true if all recorded comparisons passed.
+     */
+    pub fn is_ok(&self) -> bool {
+        return self.failures.is_empty();
+    }
+
+    /**
+    Returns the failed comparisons in the order they have been recorded.
+     */
+    pub fn failures(&self) -> &[AnyComparison] {
+        return &self.failures;
+    }
+
+    /**
+    Returns an iterator over the failed comparisons.
+     */
+    pub fn iter(&self) -> std::slice::Iter<'_, AnyComparison> {
+        return self.failures.iter();
+    }
+
+    /**
+    Returns `Ok(())` if all recorded comparisons passed and the set itself
+    otherwise.
+     */
+    pub fn into_result(self) -> Result<(), Self> {
+        if self.is_ok() {
+            return Ok(());
+        } else {
+            return Err(self);
+        }
+    }
+
+    /**
+    Returns an object which displays the set using the given `style` for all
+    comparisons instead of the [default style](default_style). The header is
+    taken from the [catalog](MessageStyle::catalog) of the style.
+
+    ```
+    use compare_variables::{compare_variables, ComparisonSet, German, LocalizedStyle};
+
+    let x = 3;
+    let mut set = ComparisonSet::new();
+    set.check(compare_variables!(x > 10));
+    assert_eq!(
+        set.display_with(&LocalizedStyle::new(&German)).to_string(),
+        "1 von 1 Vergleichen fehlgeschlagen:\n- x muss größer als 10 sein, war aber 3"
+    );
+    ```
+     */
+    pub fn display_with<'a>(&'a self, style: &'a dyn MessageStyle) -> impl std::fmt::Display + 'a {
+        return StyledSet { set: self, style };
+    }
+
+    fn fmt_with_style(
+        &self,
+        style: &dyn MessageStyle,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        let key = if self.is_ok() {
+            "set.passed"
+        } else {
+            "set.failed"
+        };
+        write_template(f, style.catalog().phrase(key), &mut |argument, f| {
+            match argument {
+                "failed" => write!(f, "{}", self.failed())?,
+                "total" => write!(f, "{}", self.len())?,
+                _ => (),
+            }
+            return Ok(());
+        })?;
+        for failure in self.failures.iter() {
+            if f.alternate() {
+                write!(f, "\n- {:#}", failure.display_with(style))?;
+            } else {
+                write!(f, "\n- {}", failure.display_with(style))?;
+            }
+        }
+        return Ok(());
+    }
+}
+
+impl std::fmt::Display for ComparisonSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return self.fmt_with_style(default_style(), f);
+    }
+}
+
+/// A [`ComparisonSet`] which is displayed in a specific [`MessageStyle`].
+struct StyledSet<'a> {
+    set: &'a ComparisonSet,
+    style: &'a dyn MessageStyle,
+}
+
+impl std::fmt::Display for StyledSet<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return self.set.fmt_with_style(self.style, f);
+    }
+}
+
+impl std::error::Error for ComparisonSet {}
+
+impl<C: Into<AnyComparison>> Extend<C> for ComparisonSet {
+    fn extend<I: IntoIterator<Item = C>>(&mut self, iter: I) {
+        for comparison in iter {
+            self.push(comparison);
+        }
+    }
+}
+
+impl IntoIterator for ComparisonSet {
+    type Item = AnyComparison;
+    type IntoIter = std::vec::IntoIter<AnyComparison>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.failures.into_iter();
+    }
+}
+
+impl<'a> IntoIterator for &'a ComparisonSet {
+    type Item = &'a AnyComparison;
+    type IntoIter = std::slice::Iter<'a, AnyComparison>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.failures.iter();
+    }
+}
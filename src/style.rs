@@ -0,0 +1,563 @@
+/*!
+Pluggable rendering of [`Comparison`](crate::Comparison) messages, see
+[`MessageStyle`].
+ */
+
+use std::fmt::{Debug, Formatter, Result};
+use std::sync::{PoisonError, RwLock};
+
+use crate::locale::write_template;
+use crate::{ComparisonOperator, ComparisonOutcome, English, MessageCatalog, Tolerance};
+
+/**
+Defines how a [`Comparison`](crate::Comparison) is rendered into a message.
+
+The style only renders the comparison itself. A custom message (see
+[`Comparison::with_message`](crate::Comparison::with_message)) and the source
+location (see [`Comparison::with_location`](crate::Comparison::with_location))
+are appended independently of the style.
+
+This crate provides the following styles:
+- [`ExpressionStyle`] (default): `` `x (value: 3) > 10` is false ``
+- [`NaturalLanguageStyle`]: `x must be greater than 10, but was 3`
+
+The style can be selected per call via
+[`Comparison::display_with`](crate::Comparison::display_with) or globally via
+[`set_default_style`].
+
+# Examples
+
+A custom style which only reports the verdict:
+```
+use std::fmt::{Formatter, Result};
+use compare_variables::{compare_variables, ComparisonOutcome, ComparisonView, MessageStyle};
+
+struct Verdict;
+
+impl MessageStyle for Verdict {
+    fn fmt(&self, comparison: &ComparisonView<'_>, f: &mut Formatter<'_>) -> Result {
+        match comparison.outcome() {
+            ComparisonOutcome::True => write!(f, "ok"),
+            _ => write!(f, "not ok"),
+        }
+    }
+}
+
+let x = 3;
+let err = compare_variables!(x > 10).unwrap_err();
+assert_eq!(err.display_with(&Verdict).to_string(), "not ok");
+```
+ */
+pub trait MessageStyle: Send + Sync {
+    /**
+    Writes the message of the given `comparison` into the formatter.
+     */
+    fn fmt(&self, comparison: &ComparisonView<'_>, f: &mut Formatter<'_>) -> Result;
+
+    /**
+    Returns the [`MessageCatalog`] for phrases which are written around the
+    comparisons, e.g. the header of a [`ComparisonSet`](crate::ComparisonSet).
+    Defaults to [`English`].
+     */
+    fn catalog(&self) -> &dyn MessageCatalog {
+        return &English;
+    }
+}
+
+/**
+Type-erased access to the data of a [`Comparison`](crate::Comparison) for
+implementing a [`MessageStyle`]. An instance is obtained via
+[`Comparison::view`](crate::Comparison::view).
+ */
+#[derive(Clone, Copy)]
+pub struct ComparisonView<'a> {
+    comparison: &'a dyn ErasedComparison,
+}
+
+/// Object-safe subset of the [`Comparison`](crate::Comparison) methods.
+pub(crate) trait ErasedComparison {
+    fn len(&self) -> usize;
+    fn value(&self, index: usize) -> Option<&dyn Debug>;
+    fn variable_name(&self, index: usize) -> Option<&str>;
+    fn operator(&self, index: usize) -> Option<ComparisonOperator>;
+    fn outcome(&self) -> ComparisonOutcome;
+    fn link_outcome(&self, index: usize) -> Option<ComparisonOutcome>;
+    fn link_deviation(&self, index: usize) -> Option<f64>;
+    fn link_tolerance(&self, index: usize) -> Option<Tolerance>;
+    fn range_element(&self) -> Option<usize>;
+    fn fmt_expression(&self, f: &mut Formatter<'_>) -> Result;
+}
+
+impl<'a> ComparisonView<'a> {
+    pub(crate) fn new(comparison: &'a dyn ErasedComparison) -> Self {
+        return Self { comparison };
+    }
+
+    /**
+    Returns the number of values in the comparison chain.
+     */
+    pub fn len(&self) -> usize {
+        return self.comparison.len();
+    }
+
+    /**
+    Returns true if the comparison chain is empty, which is never the case.
+     */
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    /**
+    Returns the value at `index` within the chain, if it exists.
+     */
+    pub fn value(&self, index: usize) -> Option<&'a dyn Debug> {
+        return self.comparison.value(index);
+    }
+
+    /**
+    Returns the variable name of the value at `index` within the chain, if the
+    value exists and has a name.
+     */
+    pub fn variable_name(&self, index: usize) -> Option<&str> {
+        return self.comparison.variable_name(index);
+    }
+
+    /**
+    Returns the comparison operator between the value at `index` and its
+    successor, if it exists.
+     */
+    pub fn operator(&self, index: usize) -> Option<ComparisonOperator> {
+        return self.comparison.operator(index);
+    }
+
+    /**
+    Returns the outcome of the entire comparison.
+     */
+    pub fn outcome(&self) -> ComparisonOutcome {
+        return self.comparison.outcome();
+    }
+
+    /**
+    Returns the outcome of the comparison between the value at `index` and its
+    successor, if it exists.
+     */
+    pub fn link_outcome(&self, index: usize) -> Option<ComparisonOutcome> {
+        return self.comparison.link_outcome(index);
+    }
+
+    /**
+    Returns the deviation between the value at `index` and its successor, if
+    they have been compared approximately.
+     */
+    pub fn link_deviation(&self, index: usize) -> Option<f64> {
+        return self.comparison.link_deviation(index);
+    }
+
+    /**
+    Returns the tolerance of the link between the value at `index` and its
+    successor, if they have been compared approximately.
+     */
+    pub fn link_tolerance(&self, index: usize) -> Option<Tolerance> {
+        return self.comparison.link_tolerance(index);
+    }
+
+    /**
+    Returns the index of the value which is checked for range membership, if
+    the comparison is a range membership. The other values of the chain are
+    the bounds of the range.
+     */
+    pub fn range_element(&self) -> Option<usize> {
+        return self.comparison.range_element();
+    }
+
+    /**
+    Returns the indices of all links whose outcome is not true.
+     */
+    pub fn failed_links(&self) -> impl Iterator<Item = usize> + '_ {
+        return (0..self.len().saturating_sub(1))
+            .filter(|index| self.link_outcome(*index) != Some(ComparisonOutcome::True));
+    }
+
+    /**
+    Writes the comparison in the notation of [`ExpressionStyle`]. This can be
+    used by custom styles as a fallback.
+     */
+    pub fn fmt_expression(&self, f: &mut Formatter<'_>) -> Result {
+        return self.comparison.fmt_expression(f);
+    }
+}
+
+/**
+The default [`MessageStyle`], which writes the comparison as an expression
+followed by the verdict, e.g. `` `x (value: 3) > 10` is false ``.
+
+The verdict and the annotations (the failed links and the deviation of
+approximate comparisons) are always written in English, see
+[`MessageCatalog`] for the localizable styles.
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ExpressionStyle;
+
+impl MessageStyle for ExpressionStyle {
+    fn fmt(&self, comparison: &ComparisonView<'_>, f: &mut Formatter<'_>) -> Result {
+        return comparison.fmt_expression(f);
+    }
+}
+
+/**
+A [`MessageStyle`] which describes the comparison in plain English and is
+suited for messages shown to end users. For other languages, see
+[`LocalizedStyle`].
+
+Chains of three values with the same direction (e.g. `0 < x <= 1`) and range
+memberships are described as "between" statements. For all other chains, the
+failed links (or all links if the comparison is true) are described one by one.
+The subject of each statement is the checked value of a range membership or
+otherwise the first value with a variable name.
+
+# Examples
+```
+use compare_variables::{compare_variables, NaturalLanguageStyle};
+
+let x = 3;
+let err = compare_variables!(x > 10).unwrap_err();
+assert_eq!(
+    err.display_with(&NaturalLanguageStyle).to_string(),
+    "x must be greater than 10, but was 3"
+);
+
+let x = 2.0;
+let err = compare_variables!(0.0 < x < 1.0).unwrap_err();
+assert_eq!(
+    err.display_with(&NaturalLanguageStyle).to_string(),
+    "x must be between 0.0 and 1.0 (exclusive), but was 2.0"
+);
+
+let err = compare_variables!(x in 0.0..=1.0).unwrap_err();
+assert_eq!(
+    err.display_with(&NaturalLanguageStyle).to_string(),
+    "x must be between 0.0 and 1.0 (inclusive), but was 2.0"
+);
+
+const MAX: f64 = 5.0;
+let ok = compare_variables!(x <= MAX).unwrap();
+assert_eq!(
+    ok.display_with(&NaturalLanguageStyle).to_string(),
+    "x is 2.0, which is less than or equal to MAX (5.0)"
+);
+```
+ */
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NaturalLanguageStyle;
+
+impl MessageStyle for NaturalLanguageStyle {
+    fn fmt(&self, comparison: &ComparisonView<'_>, f: &mut Formatter<'_>) -> Result {
+        return LocalizedStyle::new(&English).fmt(comparison, f);
+    }
+}
+
+/**
+A [`MessageStyle`] which describes the comparison like
+[`NaturalLanguageStyle`], but takes all phrases from a [`MessageCatalog`].
+
+# Examples
+```
+use compare_variables::{compare_variables, German, LocalizedStyle};
+
+let x = 3;
+let err = compare_variables!(x > 10).unwrap_err();
+assert_eq!(
+    err.display_with(&LocalizedStyle::new(&German)).to_string(),
+    "x muss größer als 10 sein, war aber 3"
+);
+```
+ */
+#[derive(Clone, Copy)]
+pub struct LocalizedStyle<'a> {
+    catalog: &'a dyn MessageCatalog,
+}
+
+impl<'a> LocalizedStyle<'a> {
+    /**
+    Returns a new style which takes its phrases from the given `catalog`.
+     */
+    pub const fn new(catalog: &'a dyn MessageCatalog) -> Self {
+        return Self { catalog };
+    }
+}
+
+impl MessageStyle for LocalizedStyle<'_> {
+    fn fmt(&self, comparison: &ComparisonView<'_>, f: &mut Formatter<'_>) -> Result {
+        if let Some(between) = Between::new(comparison) {
+            return between.fmt(comparison, self.catalog, f);
+        }
+
+        // A single value without links is trivially true
+        if comparison.len() < 2 {
+            return fmt_trivial(comparison, self.catalog, f);
+        }
+
+        let is_true = comparison.outcome() == ComparisonOutcome::True;
+        let links: Vec<usize> = if is_true {
+            (0..comparison.len().saturating_sub(1)).collect()
+        } else {
+            comparison.failed_links().collect()
+        };
+        for (n, index) in links.into_iter().enumerate() {
+            if n > 0 {
+                write!(f, "{}", self.catalog.phrase("separator"))?;
+            }
+            fmt_link(comparison, index, self.catalog, f)?;
+        }
+        return Ok(());
+    }
+
+    fn catalog(&self) -> &dyn MessageCatalog {
+        return self.catalog;
+    }
+}
+
+/// A chain of three values where the middle one lies between the outer ones.
+struct Between {
+    lower: usize,
+    lower_inclusive: bool,
+    upper: usize,
+    upper_inclusive: bool,
+}
+
+impl Between {
+    fn new(comparison: &ComparisonView<'_>) -> Option<Self> {
+        if comparison.len() != 3 || comparison.range_element().is_some_and(|index| index != 1) {
+            return None;
+        }
+        let first = comparison.operator(0)?;
+        let second = comparison.operator(1)?;
+        match (first, second) {
+            (
+                ComparisonOperator::Lesser | ComparisonOperator::LesserOrEqual,
+                ComparisonOperator::Lesser | ComparisonOperator::LesserOrEqual,
+            ) => {
+                return Some(Between {
+                    lower: 0,
+                    lower_inclusive: first == ComparisonOperator::LesserOrEqual,
+                    upper: 2,
+                    upper_inclusive: second == ComparisonOperator::LesserOrEqual,
+                });
+            }
+            (
+                ComparisonOperator::Greater | ComparisonOperator::GreaterOrEqual,
+                ComparisonOperator::Greater | ComparisonOperator::GreaterOrEqual,
+            ) => {
+                return Some(Between {
+                    lower: 2,
+                    lower_inclusive: second == ComparisonOperator::GreaterOrEqual,
+                    upper: 0,
+                    upper_inclusive: first == ComparisonOperator::GreaterOrEqual,
+                });
+            }
+            _ => return None,
+        }
+    }
+
+    fn fmt(
+        &self,
+        comparison: &ComparisonView<'_>,
+        catalog: &dyn MessageCatalog,
+        f: &mut Formatter<'_>,
+    ) -> Result {
+        let inclusivity = |inclusive: bool| {
+            if inclusive {
+                return catalog.phrase("bound.inclusive");
+            } else {
+                return catalog.phrase("bound.exclusive");
+            }
+        };
+        let relation = |f: &mut Formatter<'_>| -> Result {
+            return write_template(f, catalog.phrase("between"), &mut |argument, f| {
+                match argument {
+                    "lower" => {
+                        fmt_reference(comparison, self.lower, f)?;
+                        if self.lower_inclusive != self.upper_inclusive {
+                            write!(f, " ({})", inclusivity(self.lower_inclusive))?;
+                        }
+                    }
+                    "upper" => {
+                        fmt_reference(comparison, self.upper, f)?;
+                        write!(f, " ({})", inclusivity(self.upper_inclusive))?;
+                    }
+                    _ => (),
+                }
+                return Ok(());
+            });
+        };
+        return fmt_statement(comparison, 1, comparison.outcome(), catalog, &relation, f);
+    }
+}
+
+/// Describes the link between the value at `index` and its successor.
+fn fmt_link(
+    comparison: &ComparisonView<'_>,
+    index: usize,
+    catalog: &dyn MessageCatalog,
+    f: &mut Formatter<'_>,
+) -> Result {
+    let Some(operator) = comparison.operator(index) else {
+        return Ok(());
+    };
+    let outcome = comparison
+        .link_outcome(index)
+        .unwrap_or(ComparisonOutcome::Incomparable);
+
+    // The subject of the statement is the checked value of a range membership
+    // or otherwise the first value with a variable name.
+    let flip = match comparison.range_element() {
+        Some(element) => element == index + 1,
+        None => {
+            comparison.variable_name(index).is_none()
+                && comparison.variable_name(index + 1).is_some()
+        }
+    };
+    let (subject, reference, operator) = if flip {
+        (index + 1, index, mirror(operator))
+    } else {
+        (index, index + 1, operator)
+    };
+
+    let tolerance = comparison.link_tolerance(index);
+    let relation = |f: &mut Formatter<'_>| -> Result {
+        let phrase = match tolerance {
+            Some(_) => catalog.phrase("operator.approx_equal"),
+            None => catalog.operator(operator),
+        };
+        write_template(f, phrase, &mut |argument, f| {
+            if argument == "reference" {
+                fmt_reference(comparison, reference, f)?;
+            }
+            return Ok(());
+        })?;
+        if let Some(tolerance) = tolerance {
+            let (key, limit): (_, &dyn Debug) = match &tolerance {
+                Tolerance::Absolute(limit) => ("tolerance.absolute", limit),
+                Tolerance::Relative(limit) => ("tolerance.relative", limit),
+                Tolerance::Ulps(limit) => ("tolerance.ulps", limit),
+            };
+            write!(f, " (")?;
+            write_template(f, catalog.phrase(key), &mut |argument, f| {
+                if argument == "limit" {
+                    write!(f, "{limit:?}")?;
+                }
+                return Ok(());
+            })?;
+            write!(f, ")")?;
+        }
+        return Ok(());
+    };
+    return fmt_statement(comparison, subject, outcome, catalog, &relation, f);
+}
+
+/// Returns the operator which yields the same result if its operands are swapped.
+fn mirror(operator: ComparisonOperator) -> ComparisonOperator {
+    match operator {
+        ComparisonOperator::Lesser => return ComparisonOperator::Greater,
+        ComparisonOperator::LesserOrEqual => return ComparisonOperator::GreaterOrEqual,
+        ComparisonOperator::GreaterOrEqual => return ComparisonOperator::LesserOrEqual,
+        ComparisonOperator::Greater => return ComparisonOperator::Lesser,
+        other => return other,
+    }
+}
+
+/// Writes a value which the subject is compared to, e.g. `MAX (5)` or `5`.
+fn fmt_reference(comparison: &ComparisonView<'_>, index: usize, f: &mut Formatter<'_>) -> Result {
+    let Some(value) = comparison.value(index) else {
+        return Ok(());
+    };
+    match comparison.variable_name(index) {
+        Some(name) => return write!(f, "{name} ({value:?})"),
+        None => return write!(f, "{value:?}"),
+    }
+}
+
+/**
+Writes a statement about the value at `subject`, where `relation` describes
+the required relation to the other values (e.g. "greater than 10").
+ */
+fn fmt_statement(
+    comparison: &ComparisonView<'_>,
+    subject: usize,
+    outcome: ComparisonOutcome,
+    catalog: &dyn MessageCatalog,
+    relation: &dyn Fn(&mut Formatter<'_>) -> Result,
+    f: &mut Formatter<'_>,
+) -> Result {
+    let Some(value) = comparison.value(subject) else {
+        return Ok(());
+    };
+    let name = comparison.variable_name(subject);
+    let template = catalog.verdict(outcome, name.is_some());
+    return write_template(f, template, &mut |argument, f| {
+        match argument {
+            "subject" => write!(f, "{}", name.unwrap_or_default())?,
+            "value" => write!(f, "{value:?}")?,
+            "relation" => relation(f)?,
+            _ => (),
+        }
+        return Ok(());
+    });
+}
+
+/// Writes a statement about the single value of a comparison without links.
+fn fmt_trivial(
+    comparison: &ComparisonView<'_>,
+    catalog: &dyn MessageCatalog,
+    f: &mut Formatter<'_>,
+) -> Result {
+    let Some(value) = comparison.value(0) else {
+        return Ok(());
+    };
+    let name = comparison.variable_name(0);
+    let template = match name {
+        Some(_) => catalog.phrase("verdict.trivial"),
+        None => catalog.phrase("verdict.trivial.unnamed"),
+    };
+    return write_template(f, template, &mut |argument, f| {
+        match argument {
+            "subject" => write!(f, "{}", name.unwrap_or_default())?,
+            "value" => write!(f, "{value:?}")?,
+            _ => (),
+        }
+        return Ok(());
+    });
+}
+
+static DEFAULT_STYLE: RwLock<&'static dyn MessageStyle> = RwLock::new(&ExpressionStyle);
+
+/**
+Sets the [`MessageStyle`] which is used by the [`Display`](std::fmt::Display)
+implementation of [`Comparison`](crate::Comparison) for the entire program.
+The default style is [`ExpressionStyle`].
+
+# Examples
+```no_run
+use compare_variables::{compare_variables, set_default_style, NaturalLanguageStyle};
+
+set_default_style(&NaturalLanguageStyle);
+
+let x = 3;
+let err = compare_variables!(x > 10).unwrap_err();
+assert_eq!(err.to_string(), "x must be greater than 10, but was 3");
+```
+ */
+pub fn set_default_style(style: &'static dyn MessageStyle) {
+    *DEFAULT_STYLE
+        .write()
+        .unwrap_or_else(PoisonError::into_inner) = style;
+}
+
+/**
+Returns the [`MessageStyle`] which is used by the
+[`Display`](std::fmt::Display) implementation of
+[`Comparison`](crate::Comparison), see [`set_default_style`].
+ */
+pub fn default_style() -> &'static dyn MessageStyle {
+    return *DEFAULT_STYLE.read().unwrap_or_else(PoisonError::into_inner);
+}
@@ -0,0 +1,119 @@
+/*!
+Validation of structs via per-field comparisons, see [`Validate`].
+ */
+
+use std::borrow::Cow;
+
+use crate::ComparisonSet;
+
+/**
+A type whose values can be validated by a set of comparisons.
+
+This trait is usually implemented via `#[derive(Validate)]` (requires the
+feature `proc_macro`, which is enabled by default). The derive macro generates
+[`Validate::validate_into`] from the following field attributes:
+
+- `#[check(...)]`: A comma-separated list of comparisons in the syntax of
+  [`validate`](crate::validate). The placeholder `_` stands for the annotated
+  field and other fields can be referenced via `self.field`. Field operands are
+  named after their path within the validated value.
+- `#[validate]`: The field itself implements [`Validate`]. Its comparisons are
+  recorded as well, with the names being prefixed by the name of the field.
+
+All operands are borrowed, therefore failed comparisons are converted via
+[`Comparison::into_owned`](crate::Comparison::into_owned), which requires the
+compared types to implement [`Clone`].
+
+# Examples
+```
+use compare_variables::Validate;
+
+#[derive(Validate)]
+struct Limits {
+    min: u32,
+    #[check(_ >= self.min)]
+    max: u32,
+}
+
+#[derive(Validate)]
+struct Config {
+    #[check(0.0 < _ <= 1.0)]
+    ratio: f64,
+    #[validate]
+    limits: Limits,
+}
+
+let config = Config { ratio: 0.5, limits: Limits { min: 1, max: 10 } };
+assert!(config.validate().is_ok());
+
+let config = Config { ratio: 1.5, limits: Limits { min: 10, max: 1 } };
+let err = config.validate().unwrap_err();
+assert_eq!(
+    err.to_string(),
+    "2 of 2 comparisons failed:\n\
+     - `0.0 < ratio (value: 1.5) <= 1.0` is false (failed: `ratio (value: 1.5) <= 1.0`)\n\
+     - `limits.max (value: 1) >= limits.min (value: 10)` is false"
+);
+```
+ */
+pub trait Validate {
+    /**
+    Records the comparisons of `self` in `set`. The variable names of the
+    fields are prefixed with `path` (see [`field_path`]), which is empty for
+    the value being validated and the path of the field for nested values.
+     */
+    fn validate_into(&self, path: &str, set: &mut ComparisonSet);
+
+    /**
+    Returns `Ok(())` if all comparisons of `self` are true and the set of all
+    comparisons otherwise.
+     */
+    fn validate(&self) -> Result<(), ComparisonSet> {
+        let mut set = ComparisonSet::new();
+        self.validate_into("", &mut set);
+        return set.into_result();
+    }
+}
+
+impl<T: Validate + ?Sized> Validate for &T {
+    fn validate_into(&self, path: &str, set: &mut ComparisonSet) {
+        (**self).validate_into(path, set);
+    }
+}
+
+impl<T: Validate + ?Sized> Validate for Box<T> {
+    fn validate_into(&self, path: &str, set: &mut ComparisonSet) {
+        (**self).validate_into(path, set);
+    }
+}
+
+impl<T: Validate> Validate for Option<T> {
+    /**
+    Validates the value, if it exists.
+     */
+    fn validate_into(&self, path: &str, set: &mut ComparisonSet) {
+        if let Some(value) = self {
+            value.validate_into(path, set);
+        }
+    }
+}
+
+/**
+Returns the path of `field` within the value at `path`, which is `field` itself
+if `path` is empty and `path.field` otherwise.
+
+The field itself is borrowed, only joined paths are allocated.
+
+```
+use compare_variables::field_path;
+
+assert_eq!(field_path("", "ratio"), "ratio");
+assert_eq!(field_path("config.limits", "max"), "config.limits.max");
+```
+ */
+pub fn field_path(path: &str, field: &'static str) -> Cow<'static, str> {
+    if path.is_empty() {
+        return Cow::Borrowed(field);
+    }
+    return Cow::Owned(format!("{path}.{field}"));
+}
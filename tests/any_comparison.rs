@@ -0,0 +1,94 @@
+use compare_variables::{
+    AnyComparison, Comparison, ComparisonErrorTrait, ComparisonReport, compare_variables,
+};
+
+fn check(count: i32, ratio: f64, name: String) -> Result<(), AnyComparison> {
+    compare_variables!(count > 0)?;
+    compare_variables!(0.0 <= ratio <= 1.0)?;
+    compare_variables!(name != String::new())?;
+    return Ok(());
+}
+
+#[test]
+fn test_question_mark_conversion() {
+    assert!(check(1, 0.5, "a".into()).is_ok());
+
+    let err = check(0, 0.5, "a".into()).unwrap_err();
+    assert_eq!(err.to_string(), "`count (value: 0) > 0` is false");
+    assert!(err.is::<ComparisonReport>());
+
+    let err = check(1, 1.5, "a".into()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`0.0 <= ratio (value: 1.5) <= 1.0` is false (failed: `ratio (value: 1.5) <= 1.0`)"
+    );
+    assert_eq!(err.len(), 3);
+
+    let err = check(1, 0.5, String::new()).unwrap_err();
+    assert_eq!(err.variable_name(0), Some("name"));
+    assert_eq!(err.value_debug(0).as_deref(), Some("\"\""));
+}
+
+#[test]
+fn test_owned_conversion() {
+    let x = 3u8;
+    let err: AnyComparison = compare_variables!(owned x > 4).unwrap_err().into();
+    assert!(err.is::<ComparisonReport>());
+    assert_eq!(err.value_debug(0).as_deref(), Some("3"));
+
+    let err = AnyComparison::new(compare_variables!(owned x > 4).unwrap_err());
+    assert!(err.is::<Comparison<u8>>());
+
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Version(u32);
+    let version = Version(1);
+    let err = AnyComparison::new(compare_variables!(owned version >= Version(2)).unwrap_err());
+    assert!(err.is::<Comparison<Version>>());
+    assert_eq!(
+        err.to_string(),
+        "`version (value: Version(1)) >= Version(2) (value: Version(2))` is false"
+    );
+}
+
+#[test]
+fn test_user_defined_type() {
+    #[derive(Debug, Clone, PartialEq, PartialOrd)]
+    struct Meters(f64);
+
+    fn check(a: Meters, b: Meters) -> Result<(), AnyComparison> {
+        compare_variables!(a > Meters(1.0))?;
+        compare_variables!(b < Meters(10.0))?;
+        return Ok(());
+    }
+
+    assert!(check(Meters(2.0), Meters(5.0)).is_ok());
+
+    let err = check(Meters(0.5), Meters(5.0)).unwrap_err();
+    assert!(err.is::<ComparisonReport>());
+    assert_eq!(
+        err.to_string(),
+        "`a (value: Meters(0.5)) > Meters(1.0) (value: Meters(1.0))` is false"
+    );
+
+    let err = check(Meters(2.0), Meters(20.0)).unwrap_err();
+    assert_eq!(err.variable_name(0), Some("b"));
+    assert_eq!(err.value_debug(0).as_deref(), Some("Meters(20.0)"));
+}
+
+#[test]
+fn test_conversion_into_other_errors() {
+    let err = check(0, 0.5, "a".into()).unwrap_err();
+    let boxed: Box<dyn ComparisonErrorTrait> = err.into();
+    let err = AnyComparison::from(boxed);
+    let cmp = err.into_inner().downcast::<ComparisonReport>().unwrap();
+    assert_eq!(cmp.first_val().value.as_str(), "0");
+
+    fn generic() -> Result<(), Box<dyn std::error::Error>> {
+        check(0, 0.5, "a".into())?;
+        return Ok(());
+    }
+    assert_eq!(
+        generic().unwrap_err().to_string(),
+        "`count (value: 0) > 0` is false"
+    );
+}
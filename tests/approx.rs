@@ -0,0 +1,95 @@
+use compare_variables::{
+    Comparison, ComparisonOperator, ComparisonOutcome, ComparisonValue, Tolerance,
+    compare_variables,
+};
+
+#[test]
+fn test_absolute_tolerance() {
+    let x = 0.1 + 0.2;
+    assert!(compare_variables!(x == 0.3).is_err());
+    assert!(compare_variables!(x ~= 0.3, abs = 1e-9).is_ok());
+
+    let err = compare_variables!(x ~= 0.4, abs = 1e-9).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 0.30000000000000004) ~= 0.4` is false \
+        (absolute deviation: 0.09999999999999998, tolerance: 1e-9)"
+    );
+    assert_eq!(err.link_deviation(0), Some(0.09999999999999998));
+    assert_eq!(err.operator(0), Some(ComparisonOperator::Equal));
+    assert_eq!(err.link_tolerance(0), Some(Tolerance::Absolute(1e-9)));
+
+    let tol = 0.5f32;
+    let y = 2.0f32;
+    assert!(compare_variables!(y ~= 2.4, abs = tol as f64).is_ok());
+}
+
+#[test]
+fn test_relative_tolerance() {
+    let x = 100.0;
+    let y = 101.0;
+    assert!(compare_variables!(x ~= y, rel = 0.01).is_ok());
+
+    let err = compare_variables!(x ~= y, rel = 0.001).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 100.0) ~= y (value: 101.0)` is false \
+        (relative deviation: 0.009900990099009901, tolerance: 0.001)"
+    );
+}
+
+#[test]
+fn test_ulps_tolerance() {
+    let x = 1.0f64;
+    let y = 1.0 + 2.0 * f64::EPSILON;
+    assert!(compare_variables!(x ~= y, ulps = 2).is_ok());
+
+    let err = compare_variables!(x ~= y, ulps = 1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 1.0) ~= y (value: 1.0000000000000004)` is false \
+        (deviation: 2 ULPs, tolerance: 1 ULPs)"
+    );
+
+    // ULPs across zero
+    let a = -0.0f32;
+    let b = f32::from_bits(1);
+    assert!(compare_variables!(a ~= b, ulps = 1).is_ok());
+}
+
+#[test]
+fn test_incomparable() {
+    let x = f64::NAN;
+    let err = compare_variables!(x ~= 1.0, abs = 1.0).unwrap_err();
+    assert_eq!(err.outcome(), ComparisonOutcome::Incomparable);
+    assert_eq!(
+        err.to_string(),
+        "`x (value: NaN) ~= 1.0` is undecidable: values are not comparable"
+    );
+}
+
+#[test]
+fn test_exact_and_approximate_links() {
+    let x = 1.0 + 1e-12;
+    let exact = Comparison::new(
+        ComparisonValue::new(x, Some("x")),
+        [(ComparisonOperator::Equal, ComparisonValue::new(1.0, None))],
+    );
+    assert!(!exact.is_true());
+    assert_eq!(exact.link_tolerance(0), None);
+    assert_eq!(exact.link_deviation(0), None);
+
+    let approx = Comparison::new_approx(
+        ComparisonValue::new(x, Some("x")),
+        Tolerance::Absolute(1e-9),
+        ComparisonValue::new(1.0, None),
+    );
+    assert!(approx.is_true());
+    assert_eq!(approx.operator(0), Some(ComparisonOperator::Equal));
+    assert_eq!(approx.link_tolerance(0), Some(Tolerance::Absolute(1e-9)));
+    assert_eq!(
+        approx.to_string(),
+        "`x (value: 1.000000000001) ~= 1.0` is true \
+        (absolute deviation: 1.000088900582341e-12, tolerance: 1e-9)"
+    );
+}
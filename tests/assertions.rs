@@ -0,0 +1,86 @@
+use compare_variables::{assert_compare, debug_assert_compare};
+
+/// Returns the panic message of `f`.
+fn panic_message(f: impl FnOnce() + std::panic::UnwindSafe) -> String {
+    let payload = std::panic::catch_unwind(f).unwrap_err();
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return payload.downcast_ref::<&str>().unwrap().to_string();
+}
+
+#[test]
+fn test_assert_compare_passes() {
+    let x = 1;
+    let name = String::from("Bob");
+    assert_compare!(0 < x <= 1);
+    assert_compare!(x in 0..10, "x is out of range");
+    assert_compare!(name.as_str() != "", "name must not be empty");
+    assert_compare!(0.1 + 0.2 ~= 0.3, abs = 1e-9);
+    debug_assert_compare!(x == 1);
+
+    // The operands are borrowed
+    assert_eq!(name, "Bob");
+}
+
+#[test]
+fn test_assert_compare_message() {
+    let x = 2;
+    assert_eq!(
+        panic_message(|| assert_compare!(0 < x <= 1)),
+        "`0 < x (value: 2) <= 1` is false (failed: `x (value: 2) <= 1`)"
+    );
+
+    let path = "config.toml";
+    assert_eq!(
+        panic_message(|| assert_compare!(x <= 1, "invalid value in {}", path)),
+        "`x (value: 2) <= 1` is false: invalid value in config.toml"
+    );
+    assert_eq!(
+        panic_message(|| assert_compare!(x in 5.., "invalid value in {path}")),
+        "`x (value: 2) in 5..` is false: invalid value in config.toml"
+    );
+
+    // Variables named like the internal binding are not shadowed
+    let comparison = "custom";
+    assert_eq!(
+        panic_message(|| assert_compare!(x < 1, "{}", comparison)),
+        "`x (value: 2) < 1` is false: custom"
+    );
+}
+
+#[test]
+#[should_panic(expected = "`x (value: NaN) ~= 1.0` is undecidable: values are not comparable")]
+fn test_assert_compare_should_panic() {
+    let x = f64::NAN;
+    assert_compare!(x ~= 1.0, rel = 1e-3);
+}
+
+#[test]
+#[cfg_attr(
+    debug_assertions,
+    should_panic(expected = "`x (value: 1) > 1` is false")
+)]
+fn test_debug_assert_compare() {
+    let x = 1;
+    debug_assert_compare!(x > 1);
+}
+
+#[test]
+fn test_panic_location() {
+    let location = std::sync::Arc::new(std::sync::Mutex::new(None));
+    let hook_location = location.clone();
+    let previous_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        // Other tests may panic concurrently
+        if info.payload().downcast_ref::<String>().map(String::as_str) == Some("`1 > 2` is false") {
+            *hook_location.lock().unwrap() = info.location().map(|l| l.line());
+        }
+    }));
+    let line = line!() + 1;
+    let result = std::panic::catch_unwind(|| assert_compare!(1 > 2));
+    std::panic::set_hook(previous_hook);
+
+    assert!(result.is_err());
+    assert_eq!(*location.lock().unwrap(), Some(line));
+}
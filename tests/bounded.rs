@@ -0,0 +1,76 @@
+use compare_variables::{Comparison, bounded};
+
+bounded!(
+    /// A percentage between 0 and 100.
+    #[derive(Copy)]
+    pub Percent: f64 where 0.0 <= _ <= 100.0, serde
+);
+
+bounded!(Code: String where val String::from("A") <= _ < val String::from("ZZ"));
+
+bounded!(Level: u8 where _ < 10);
+
+#[test]
+fn test_construction() {
+    let percent = Percent::new(40.0).unwrap();
+    assert_eq!(percent.get(), 40.0);
+    assert_eq!(*percent, 40.0);
+    assert_eq!(f64::from(percent), 40.0);
+    assert_eq!(Percent::try_from(100.0).unwrap().into_inner(), 100.0);
+
+    let err: Comparison<f64> = Percent::try_from(-1.0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`0.0 <= Percent (value: -1.0) <= 100.0` is false (failed: `0.0 <= Percent (value: -1.0)`)"
+    );
+
+    let unchecked = Percent::new_unchecked(200.0);
+    assert_eq!(unchecked.get(), 200.0);
+}
+
+#[test]
+fn test_arithmetic() {
+    let percent = Percent::new(40.0).unwrap();
+    assert_eq!((percent + 50.0).unwrap().get(), 90.0);
+    assert_eq!((percent - *percent).unwrap().get(), 0.0);
+    assert_eq!((percent * 2.0).unwrap().get(), 80.0);
+    assert_eq!((percent / 4.0).unwrap().get(), 10.0);
+    assert_eq!((percent % 30.0).unwrap().get(), 10.0);
+    assert!((percent * 3.0).is_err());
+
+    let err = (percent - 50.0).unwrap_err();
+    assert_eq!(err.first_val().value, 0.0);
+
+    let level = Level::new(9).unwrap();
+    assert_eq!(
+        (level + 1).unwrap_err().to_string(),
+        "`Level (value: 10) < 10` is false"
+    );
+}
+
+#[test]
+fn test_non_copy_type() {
+    let code = Code::new(String::from("AB")).unwrap();
+    assert_eq!(code.len(), 2);
+    assert_eq!((code.clone() + "C").unwrap().get(), "ABC");
+    assert!(Code::new(String::from("a")).is_err());
+    assert_eq!(
+        (Code::new(String::from("Z")).unwrap() + "Z")
+            .unwrap_err()
+            .to_string(),
+        "`\"A\" <= Code (value: \"ZZ\") < \"ZZ\"` is false (failed: `Code (value: \"ZZ\") < \"ZZ\"`)"
+    );
+}
+
+#[test]
+fn test_serde() {
+    let percent: Percent = serde_json::from_str("12.5").unwrap();
+    assert_eq!(percent.get(), 12.5);
+    assert_eq!(serde_json::to_string(&percent).unwrap(), "12.5");
+
+    let err = serde_json::from_str::<Percent>("120.0").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`0.0 <= Percent (value: 120.0) <= 100.0` is false (failed: `Percent (value: 120.0) <= 100.0`)"
+    );
+}
@@ -0,0 +1,92 @@
+use compare_variables::{
+    ComparisonOperator, ConstMessage, ConstValue, compare_variables, const_compare,
+    static_assert_compare,
+};
+use std::cmp::Ordering;
+
+const MAX: u32 = 64;
+const BUFFER_SIZE: usize = 48;
+
+const _: () = static_assert_compare!(16 <= BUFFER_SIZE <= 64);
+const _: () = static_assert_compare!(5i32 >= -1i32);
+const _: () = static_assert_compare!(true != false);
+
+const fn in_range(len: u32) -> bool {
+    return const_compare!(0 < len <= MAX).is_ok();
+}
+
+fn bits<const N: usize>() -> [bool; N] {
+    static_assert_compare!(N <= 64);
+    return [false; N];
+}
+
+#[test]
+fn test_const_contexts() {
+    const VALID: bool = const_compare!(5i32 >= -1i32).is_ok();
+    const INVALID: bool = const_compare!(5 < -1).is_ok();
+    const {
+        assert!(VALID);
+        assert!(!INVALID);
+    }
+    assert!(in_range(1));
+    assert!(!in_range(65));
+    assert_eq!(bits::<64>().len(), 64);
+}
+
+#[test]
+fn test_messages_match_comparison() {
+    let len = 65u32;
+    let zero = 0u32;
+    assert_eq!(
+        const_compare!(0 < len <= MAX).unwrap_err().as_str(),
+        compare_variables!(0 < len <= MAX).unwrap_err().to_string()
+    );
+    assert_eq!(
+        const_compare!(len < zero <= MAX != 64)
+            .unwrap_err()
+            .to_string(),
+        compare_variables!(len < zero <= MAX != 64)
+            .unwrap_err()
+            .to_string()
+    );
+    assert_eq!(
+        const_compare!(i128::MIN as min == val i128::MAX)
+            .unwrap_err()
+            .as_str(),
+        format!("`min (value: {}) == {}` is false", i128::MIN, i128::MAX)
+    );
+    assert_eq!(
+        const_compare!(u128::MAX < 0).unwrap_err().as_str(),
+        compare_variables!(u128::MAX < 0).unwrap_err().to_string()
+    );
+    assert_eq!(
+        const_compare!(true == false).unwrap_err().as_str(),
+        "`true == false` is false"
+    );
+    assert_eq!(
+        const_compare!(-3 > 2).unwrap_err().as_str(),
+        compare_variables!(-3 > 2).unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_const_value() {
+    const ORDERING: Ordering = ConstValue(-3i8).ordering(-4);
+    assert_eq!(ORDERING, Ordering::Greater);
+    assert_eq!(ConstValue(false).ordering(true), Ordering::Less);
+    assert!(ComparisonOperator::Inequal.is_true_for_ordering(ConstValue(1usize).ordering(2)));
+    assert!(!ComparisonOperator::Greater.is_true_for_ordering(Ordering::Equal));
+
+    let mut message = ConstMessage::<4>::new();
+    ConstValue(123456u32).write(&mut message);
+    assert_eq!(message.as_str(), "1234");
+
+    let mut message = ConstMessage::<3>::new();
+    message.push_str("aä");
+    assert_eq!(message.as_str(), "aä");
+    message.push_str("b");
+    assert_eq!(format!("{message:?}"), "\"aä\"");
+    let mut message = ConstMessage::<2>::new();
+    message.push_str("aä");
+    assert_eq!(message.to_string(), "a");
+}
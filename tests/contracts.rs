@@ -0,0 +1,214 @@
+use compare_variables::{Comparison, ensures, requires};
+
+#[requires(0.0 <= p <= 1.0)]
+fn bernoulli_variance(p: f64) -> f64 {
+    return p * (1.0 - p);
+}
+
+#[requires(len > 0, mode = result)]
+#[requires(len <= 64, mode = result)]
+fn buffer(len: usize) -> Result<Vec<u8>, Comparison<usize>> {
+    return Ok(vec![0; len]);
+}
+
+#[ensures(ret >= input)]
+fn round_up_to_even(input: u32) -> u32 {
+    if input.is_multiple_of(2) {
+        return input;
+    }
+    return input + 1;
+}
+
+#[ensures(ret < input)]
+fn broken_decrement(input: u32) -> u32 {
+    return input;
+}
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Comparison(String),
+    Parse,
+}
+
+impl From<Comparison<u32>> for Error {
+    fn from(value: Comparison<u32>) -> Self {
+        return Error::Comparison(value.to_string());
+    }
+}
+
+impl From<Comparison<usize>> for Error {
+    fn from(value: Comparison<usize>) -> Self {
+        return Error::Comparison(value.to_string());
+    }
+}
+
+#[requires(text.len() > 0, mode = result)]
+#[ensures(ret <= 100, mode = result)]
+fn parse_percentage(text: &str) -> Result<u32, Error> {
+    let value = text.parse::<u32>().map_err(|_| Error::Parse)?;
+    return Ok(value);
+}
+
+#[requires(x != 0, mode = debug)]
+fn debug_checked(x: i32) -> i32 {
+    return x;
+}
+
+#[ensures(ret.len() > 0)]
+fn push_default(text: &mut String) -> &mut String {
+    text.push('-');
+    return text;
+}
+
+#[ensures(ret.len() > input.len())]
+fn exclaim(input: String) -> String {
+    let mut output = input.clone();
+    output.push('!');
+    return output;
+}
+
+#[ensures(ret < Some(10))]
+fn first_digit(text: &str) -> Option<u32> {
+    let first = text.chars().next()?;
+    for c in text.chars() {
+        if c == 'x' {
+            return Some(99);
+        }
+    }
+    let parse = |c: char| -> Option<u32> {
+        // Returns from the closure, not from the function
+        return c.to_digit(10);
+    };
+    return parse(first);
+}
+
+#[ensures(ret <= 100, mode = result)]
+fn parse_and_double(text: &str) -> Result<u32, Error> {
+    let value = text.parse::<u32>().map_err(|_| Error::Parse)?;
+    value.checked_mul(2).ok_or(Error::Parse)
+}
+
+#[compare_variables::ensures(ret > 0)]
+async fn async_increment(value: u32) -> u32 {
+    return value + 1;
+}
+
+struct Counter {
+    count: u32,
+    limit: u32,
+}
+
+impl Counter {
+    #[requires(self.count < self.limit)]
+    #[ensures(self.count <= self.limit)]
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+#[test]
+fn test_requires() {
+    assert_eq!(bernoulli_variance(0.5), 0.25);
+    assert_eq!(buffer(2).unwrap(), vec![0, 0]);
+    assert_eq!(
+        buffer(0).unwrap_err().to_string(),
+        "`len (value: 0) > 0` is false"
+    );
+    assert_eq!(
+        buffer(65).unwrap_err().to_string(),
+        "`len (value: 65) <= 64` is false"
+    );
+}
+
+#[test]
+#[should_panic(
+    expected = "precondition of `bernoulli_variance` violated: `0.0 <= p (value: 2.0) <= 1.0` is false"
+)]
+fn test_requires_panic() {
+    bernoulli_variance(2.0);
+}
+
+#[test]
+fn test_ensures() {
+    assert_eq!(round_up_to_even(3), 4);
+    assert_eq!(round_up_to_even(4), 4);
+}
+
+#[test]
+#[should_panic(
+    expected = "postcondition of `broken_decrement` violated: `ret (value: 1) < input (value: 1)` is false"
+)]
+fn test_ensures_panic() {
+    broken_decrement(1);
+}
+
+#[test]
+fn test_ensures_result() {
+    assert_eq!(parse_percentage("42"), Ok(42));
+    assert_eq!(parse_percentage("x"), Err(Error::Parse));
+    assert_eq!(
+        parse_percentage(""),
+        Err(Error::Comparison(String::from(
+            "`text.len() (value: 0) > 0` is false"
+        )))
+    );
+    assert_eq!(
+        parse_percentage("142"),
+        Err(Error::Comparison(String::from(
+            "`ret (value: 142) <= 100` is false"
+        )))
+    );
+}
+
+#[test]
+fn test_borrowed_return_values() {
+    let mut text = String::new();
+    push_default(&mut text).push('+');
+    assert_eq!(text, "-+");
+    assert_eq!(exclaim(String::from("hi")), "hi!");
+}
+
+#[test]
+fn test_early_returns() {
+    assert_eq!(first_digit(""), None);
+    assert_eq!(first_digit("7"), Some(7));
+    assert_eq!(first_digit("a"), None);
+    assert!(std::panic::catch_unwind(|| first_digit("1x")).is_err());
+
+    assert_eq!(parse_and_double("21"), Ok(42));
+    assert_eq!(parse_and_double("a"), Err(Error::Parse));
+    assert_eq!(
+        parse_and_double("51"),
+        Err(Error::Comparison(String::from(
+            "`ret (value: 102) <= 100` is false"
+        )))
+    );
+}
+
+#[test]
+fn test_async_functions() {
+    let mut future = std::pin::pin!(async_increment(1));
+    let mut context = std::task::Context::from_waker(std::task::Waker::noop());
+    assert_eq!(
+        future.as_mut().poll(&mut context),
+        std::task::Poll::Ready(2)
+    );
+}
+
+#[test]
+fn test_debug_mode() {
+    assert_eq!(debug_checked(1), 1);
+    if cfg!(debug_assertions) {
+        assert!(std::panic::catch_unwind(|| debug_checked(0)).is_err());
+    } else {
+        assert_eq!(debug_checked(0), 0);
+    }
+}
+
+#[test]
+fn test_methods() {
+    let mut counter = Counter { count: 0, limit: 1 };
+    counter.increment();
+    assert_eq!(counter.count, 1);
+    assert!(std::panic::catch_unwind(move || counter.increment()).is_err());
+}
@@ -0,0 +1,18 @@
+use compare_variables::{
+    ExpressionStyle, NaturalLanguageStyle, compare_variables, set_default_style,
+};
+
+// The default style is global, therefore this file only contains a single test.
+#[test]
+fn test_set_default_style() {
+    let x = 3;
+    let err = compare_variables!(x > 10).unwrap_err();
+    assert_eq!(err.to_string(), "`x (value: 3) > 10` is false");
+
+    set_default_style(&NaturalLanguageStyle);
+    assert_eq!(err.to_string(), "x must be greater than 10, but was 3");
+    assert_eq!(format!("{err:?}"), "x must be greater than 10, but was 3");
+
+    set_default_style(&ExpressionStyle);
+    assert_eq!(err.to_string(), "`x (value: 3) > 10` is false");
+}
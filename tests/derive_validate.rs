@@ -0,0 +1,123 @@
+use compare_variables::{Comparison, Validate};
+
+#[derive(Validate)]
+struct Limits {
+    min: u32,
+    #[check(_ >= self.min)]
+    max: u32,
+}
+
+#[derive(Validate)]
+struct Config {
+    #[check(0.0 < _ <= 1.0)]
+    ratio: f64,
+    #[check(_.len() > 0, _ != self.fallback)]
+    name: String,
+    fallback: String,
+    #[validate]
+    limits: Limits,
+    #[validate]
+    optional: Option<Limits>,
+}
+
+fn config() -> Config {
+    return Config {
+        ratio: 0.5,
+        name: String::from("custom"),
+        fallback: String::from("default"),
+        limits: Limits { min: 1, max: 10 },
+        optional: None,
+    };
+}
+
+#[test]
+fn test_valid() {
+    assert!(config().validate().is_ok());
+}
+
+#[test]
+fn test_field_names() {
+    let mut config = config();
+    config.ratio = 1.5;
+    config.name = String::new();
+    config.fallback = String::new();
+    let err = config.validate().unwrap_err();
+    assert_eq!(err.passed(), 1);
+    assert_eq!(
+        err.to_string(),
+        "3 of 4 comparisons failed:\n\
+         - `0.0 < ratio (value: 1.5) <= 1.0` is false (failed: `ratio (value: 1.5) <= 1.0`)\n\
+         - `self.name.len() (value: 0) > 0` is false\n\
+         - `name (value: \"\") != fallback (value: \"\")` is false"
+    );
+    assert!(err.failures()[0].is::<Comparison<f64>>());
+    assert!(err.failures()[2].is::<Comparison<String>>());
+}
+
+#[test]
+fn test_nested() {
+    let mut config = config();
+    config.limits.min = 20;
+    config.optional = Some(Limits { min: 5, max: 4 });
+    let err = config.validate().unwrap_err();
+    assert_eq!(err.failed(), 2);
+    assert_eq!(err.failures()[0].variable_name(0), Some("limits.max"));
+    assert_eq!(err.failures()[0].variable_name(1), Some("limits.min"));
+    assert_eq!(
+        err.failures()[1].to_string(),
+        "`optional.max (value: 4) >= optional.min (value: 5)` is false"
+    );
+
+    #[derive(Validate)]
+    struct Outer {
+        #[validate]
+        config: Config,
+    }
+    let err = Outer { config }.validate().unwrap_err();
+    assert_eq!(
+        err.failures()[1].variable_name(0),
+        Some("config.optional.max")
+    );
+}
+
+#[test]
+fn test_range_field() {
+    #[derive(Validate)]
+    struct Channel {
+        #[check(_ in self.allowed)]
+        level: u8,
+        allowed: std::ops::RangeInclusive<u8>,
+    }
+
+    #[derive(Validate)]
+    struct Mixer {
+        #[validate]
+        channel: Channel,
+    }
+
+    let channel = Channel {
+        level: 12,
+        allowed: 0..=10,
+    };
+    let err = Mixer { channel }.validate().unwrap_err();
+    assert_eq!(
+        err.failures()[0].to_string(),
+        "`channel.level (value: 12) in channel.allowed (value: 0..=10)` is false"
+    );
+}
+
+#[test]
+fn test_tuple_struct_and_generics() {
+    #[derive(Validate)]
+    struct Range<T: PartialOrd + Clone + std::fmt::Debug + Send + Sync + 'static>(
+        T,
+        #[check(_ > self.0)] T,
+    );
+
+    assert!(Range(1, 2).validate().is_ok());
+    let err = Range(2.0, 1.0).validate().unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "1 of 1 comparisons failed:\n- `1 (value: 1.0) > 0 (value: 2.0)` is false"
+    );
+}
@@ -0,0 +1,75 @@
+use compare_variables::{Comparison, ensure_compare};
+
+#[derive(Debug)]
+enum ConfigError {
+    Negative,
+    OutOfRange(Comparison<u32>),
+    Invalid(String),
+    Comparison(Comparison<u32>),
+}
+
+impl From<Comparison<u32>> for ConfigError {
+    fn from(value: Comparison<u32>) -> Self {
+        return ConfigError::Comparison(value);
+    }
+}
+
+fn check_offset(offset: i64) -> Result<i64, ConfigError> {
+    ensure_compare!(offset >= 0, ConfigError::Negative);
+    return Ok(offset);
+}
+
+fn check_retries(retries: u32, max: u32) -> Result<u32, ConfigError> {
+    ensure_compare!(retries <= max => ConfigError::OutOfRange);
+    ensure_compare!(retries != 3 => |c| ConfigError::Invalid(c.to_string()));
+    ensure_compare!(retries in 0..10);
+    return Ok(retries);
+}
+
+#[test]
+fn test_error_value() {
+    assert_eq!(check_offset(1).unwrap(), 1);
+    assert!(matches!(check_offset(-1), Err(ConfigError::Negative)));
+}
+
+#[test]
+fn test_error_conversion() {
+    assert_eq!(check_retries(2, 5).unwrap(), 2);
+
+    let Err(ConfigError::OutOfRange(cmp)) = check_retries(6, 5) else {
+        panic!("expected ConfigError::OutOfRange");
+    };
+    assert_eq!(
+        cmp.to_string(),
+        "`retries (value: 6) <= max (value: 5)` is false"
+    );
+
+    let Err(ConfigError::Invalid(message)) = check_retries(3, 5) else {
+        panic!("expected ConfigError::Invalid");
+    };
+    assert_eq!(message, "`retries (value: 3) != 3` is false");
+
+    let Err(ConfigError::Comparison(cmp)) = check_retries(12, 20) else {
+        panic!("expected ConfigError::Comparison");
+    };
+    assert_eq!(cmp.to_string(), "`retries (value: 12) in 0..10` is false");
+}
+
+#[test]
+fn test_boxed_error() {
+    fn check(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let len = name.len();
+        ensure_compare!(owned len > 0);
+        ensure_compare!(name != "root", "root is not allowed");
+        return Ok(());
+    }
+    assert!(check("user").is_ok());
+    assert_eq!(
+        check("").unwrap_err().to_string(),
+        "`len (value: 0) > 0` is false"
+    );
+    assert_eq!(
+        check("root").unwrap_err().to_string(),
+        "root is not allowed"
+    );
+}
@@ -0,0 +1,73 @@
+use compare_variables::compare_variables;
+
+#[test]
+fn test_method_calls() {
+    let v = [1, 2, 3];
+    assert!(compare_variables!(v.len() > 0).is_ok());
+
+    let err = compare_variables!(owned v.len() > 3).unwrap_err();
+    assert_eq!(err.to_string(), "`v.len() (value: 3) > 3` is false");
+
+    let err = compare_variables!(owned v.iter().sum::<i32>() < 6).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`v.iter().sum::<i32>() (value: 6) < 6` is false"
+    );
+
+    let err = compare_variables!(owned Vec::<u8>::new().len() > 0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`Vec::<u8>::new().len() (value: 0) > 0` is false"
+    );
+}
+
+#[test]
+fn test_indexing_and_arithmetic() {
+    let a = [1, 5, 3];
+    let i = 1;
+    let b = 4;
+    let err = compare_variables!(a[i] <= b).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`a[i] (value: 5) <= b (value: 4)` is false"
+    );
+
+    let x = 3;
+    let y = 4;
+    assert!(compare_variables!(x + 1 < y).is_err());
+    let err = compare_variables!(owned x + 1 < y).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x + 1 (value: 4) < y (value: 4)` is false"
+    );
+
+    let err = compare_variables!(owned x << 2 > y >> 1 == 3).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x << 2 (value: 12) > y >> 1 (value: 2) == 3` is false (failed: `y >> 1 (value: 2) == 3`)"
+    );
+}
+
+#[test]
+fn test_casts_and_blocks() {
+    let x = 3u8;
+    let y = 2.5;
+    let err = compare_variables!(owned(x as f64) < y).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`(x as f64) (value: 3.0) < y (value: 2.5)` is false"
+    );
+
+    // `as` outside of parentheses defines an alias
+    let err = compare_variables!(owned (x as f64) as x_float < y).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x_float (value: 3.0) < y (value: 2.5)` is false"
+    );
+
+    let err = compare_variables!(owned { x * 2 } <= 5).unwrap_err();
+    assert_eq!(err.to_string(), "`{ x * 2 } (value: 6) <= 5` is false");
+
+    let err = compare_variables!(owned val x.pow(2) < 5).unwrap_err();
+    assert_eq!(err.to_string(), "`9 < 5` is false");
+}
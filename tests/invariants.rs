@@ -0,0 +1,167 @@
+use compare_variables::{ensures, invariant};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Interval {
+    lo: i32,
+    hi: i32,
+}
+
+#[invariant(self.lo <= self.hi)]
+impl Interval {
+    fn new(lo: i32, hi: i32) -> Self {
+        return Interval { lo, hi };
+    }
+
+    fn try_new(lo: i32, hi: i32) -> Result<Self, String> {
+        if lo == i32::MIN {
+            return Err(String::from("unbounded"));
+        }
+        return Ok(Interval { lo, hi });
+    }
+
+    fn checked_new(lo: i32, hi: i32) -> Option<Interval> {
+        return Some(Interval { lo, hi });
+    }
+
+    fn set_lo(&mut self, lo: i32) {
+        self.lo = lo;
+    }
+
+    #[ensures(ret >= 0)]
+    fn shift(&mut self, offset: i32) -> i32 {
+        if offset == 0 {
+            return 0;
+        }
+        self.lo += offset;
+        self.hi += offset;
+        return offset.abs();
+    }
+
+    fn with_hi(mut self, hi: i32) -> Self {
+        self.hi = hi;
+        return self;
+    }
+
+    fn lo_mut(&mut self) -> &mut i32 {
+        return &mut self.lo;
+    }
+
+    fn bounds_mut(&mut self) -> impl Iterator<Item = &mut i32> {
+        return [&mut self.lo, &mut self.hi].into_iter();
+    }
+
+    fn shrink(&mut self) -> Option<&'static str> {
+        if self.lo == self.hi {
+            return Some("empty");
+        }
+        self.hi -= 1;
+        return None;
+    }
+
+    fn width(&self) -> i32 {
+        return self.hi - self.lo;
+    }
+
+    fn invalid() -> (i32, i32) {
+        return (1, 0);
+    }
+}
+
+struct Buffer {
+    len: usize,
+    capacity: usize,
+}
+
+#[invariant(self.len <= self.capacity)]
+#[invariant(self.capacity <= 64, mode = debug)]
+impl Buffer {
+    fn push(&mut self) {
+        self.len += 1;
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.capacity += additional;
+    }
+}
+
+fn panic_message<T>(f: impl FnOnce() -> T) -> String {
+    let payload = catch_unwind(AssertUnwindSafe(f)).err().expect("no panic");
+    return payload
+        .downcast::<String>()
+        .map(|message| *message)
+        .unwrap();
+}
+
+#[test]
+fn test_mutating_methods() {
+    let mut interval = Interval::new(1, 3);
+    interval.set_lo(2);
+    assert_eq!(interval.shift(2), 2);
+    assert_eq!(interval, Interval { lo: 4, hi: 5 });
+    assert_eq!(interval.width(), 1);
+    assert_eq!(
+        panic_message(|| interval.set_lo(6)),
+        "`self.lo (value: 6) <= self.hi (value: 5)` is false: invariant of `Interval` violated by `set_lo`"
+    );
+}
+
+#[test]
+fn test_borrowing_methods_are_not_checked() {
+    let mut interval = Interval::new(1, 3);
+    *interval.lo_mut() = 2;
+    for bound in interval.bounds_mut() {
+        *bound += 1;
+    }
+    assert_eq!(interval, Interval { lo: 3, hi: 4 });
+
+    // Not checked, as the returned value may borrow the interval
+    assert_eq!(interval.shrink(), None);
+    assert_eq!(interval.shrink(), Some("empty"));
+    *interval.lo_mut() = 5;
+    assert_eq!(interval.width(), -2);
+}
+
+#[test]
+fn test_constructors() {
+    assert_eq!(
+        panic_message(|| Interval::new(3, 1)),
+        "`self.lo (value: 3) <= self.hi (value: 1)` is false: invariant of `Interval` violated by `new`"
+    );
+    assert_eq!(
+        Interval::try_new(i32::MIN, 0),
+        Err(String::from("unbounded"))
+    );
+    assert!(Interval::try_new(0, 1).is_ok());
+    assert!(panic_message(|| Interval::try_new(1, 0)).ends_with("violated by `try_new`"));
+    assert!(panic_message(|| Interval::checked_new(1, 0)).ends_with("violated by `checked_new`"));
+    assert!(panic_message(|| Interval::new(0, 1).with_hi(-1)).ends_with("violated by `with_hi`"));
+    // Functions which do not return the type are not checked
+    assert_eq!(Interval::invalid(), (1, 0));
+}
+
+#[test]
+fn test_multiple_invariants() {
+    let mut buffer = Buffer {
+        len: 0,
+        capacity: 1,
+    };
+    buffer.push();
+    assert_eq!(
+        panic_message(|| buffer.push()),
+        "`self.len (value: 2) <= self.capacity (value: 1)` is false: invariant of `Buffer` violated by `push`"
+    );
+
+    let mut buffer = Buffer {
+        len: 0,
+        capacity: 1,
+    };
+    if cfg!(debug_assertions) {
+        assert_eq!(
+            panic_message(|| buffer.reserve(64)),
+            "`self.capacity (value: 65) <= 64` is false: invariant of `Buffer` violated by `reserve`"
+        );
+    } else {
+        buffer.reserve(64);
+    }
+}
@@ -0,0 +1,183 @@
+use compare_variables::{
+    Catalog, CatalogError, ComparisonErrorTrait, ComparisonSet, English, German, LocalizedStyle,
+    MessageCatalog, NaturalLanguageStyle, bundled_catalog, compare_variables,
+};
+
+const GERMAN: LocalizedStyle<'static> = LocalizedStyle::new(&German);
+
+#[test]
+fn test_german() {
+    let x = 3;
+    let limit = 10;
+    let err = compare_variables!(x > limit).unwrap_err();
+    assert_eq!(
+        err.display_with(&GERMAN).to_string(),
+        "x muss größer als limit (10) sein, war aber 3"
+    );
+    let ok = compare_variables!(x < limit).unwrap();
+    assert_eq!(
+        ok.display_with(&GERMAN).to_string(),
+        "x ist 3 und damit kleiner als limit (10)"
+    );
+    let err = compare_variables!(x in 0..3).unwrap_err();
+    assert_eq!(
+        err.display_with(&GERMAN).to_string(),
+        "x muss zwischen 0 (inklusive) und 3 (exklusive) sein, war aber 3"
+    );
+    let err = compare_variables!(2 < 1).unwrap_err();
+    assert_eq!(
+        err.display_with(&GERMAN).to_string(),
+        "2 ist nicht kleiner als 1"
+    );
+
+    let y = f64::NAN;
+    let err = compare_variables!(y ~= 1.0, rel = 1e-6).unwrap_err();
+    assert_eq!(
+        err.display_with(&GERMAN).to_string(),
+        "y muss ungefähr gleich 1.0 (relative Toleranz: 1e-6) sein, war aber NaN \
+        (Werte sind nicht vergleichbar)"
+    );
+}
+
+#[test]
+fn test_english_matches_natural_language() {
+    let x = 0.5;
+    let err = compare_variables!(0.0 < x <= 0.25, "x is too large").unwrap_err();
+    assert_eq!(
+        err.display_with(&LocalizedStyle::new(&English)).to_string(),
+        err.display_with(&NaturalLanguageStyle).to_string()
+    );
+}
+
+#[test]
+fn test_trait_object() {
+    let x = 3;
+    let err: Box<dyn ComparisonErrorTrait> =
+        Box::new(compare_variables!(owned x > 10, "not enough").unwrap_err());
+    assert_eq!(
+        err.display_with(&GERMAN).to_string(),
+        "x muss größer als 10 sein, war aber 3: not enough"
+    );
+    assert_eq!(
+        err.display_with(&NaturalLanguageStyle).to_string(),
+        "x must be greater than 10, but was 3: not enough"
+    );
+}
+
+#[test]
+fn test_bundled_catalog() {
+    let catalog = bundled_catalog("de-CH").unwrap();
+    assert_eq!(catalog.phrase("bound.inclusive"), "inklusive");
+    let catalog = bundled_catalog("EN").unwrap();
+    assert_eq!(catalog.phrase("bound.inclusive"), "inclusive");
+    assert!(bundled_catalog("fr").is_none());
+    assert!(bundled_catalog("").is_none());
+}
+
+#[test]
+fn test_parse_catalog() {
+    let catalog = Catalog::parse(
+        "
+        # Comment
+        operator.greater = supérieur à {reference}
+        separator = \" / \"
+        verdict.false={subject} doit être {relation}, mais valait {value}
+        ",
+    )
+    .unwrap();
+    assert_eq!(catalog.lookup("separator"), Some(" / "));
+    assert_eq!(catalog.lookup("operator.lesser"), None);
+    assert_eq!(catalog.phrase("operator.lesser"), "less than {reference}");
+
+    let (a, b, c) = (3, 2, 1);
+    let err = compare_variables!(a > b < c > 5).unwrap_err();
+    assert_eq!(
+        err.display_with(&LocalizedStyle::new(&catalog)).to_string(),
+        "b doit être less than c (1), mais valait 2 / c doit être supérieur à 5, mais valait 1"
+    );
+
+    let Err(CatalogError::Syntax { line: 3 }) = Catalog::parse("\nbetween = a\nbetween b") else {
+        panic!()
+    };
+    let Err(CatalogError::UnknownKey { line: 1, key }) = Catalog::parse("operator.bigger = x")
+    else {
+        panic!()
+    };
+    assert_eq!(key, "operator.bigger");
+}
+
+#[test]
+fn test_load_catalog() {
+    let path = std::env::temp_dir().join(format!(
+        "compare_variables_catalog_{}.txt",
+        std::process::id()
+    ));
+    std::fs::write(&path, "bound.inclusive = incluse\n").unwrap();
+    let catalog = Catalog::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(catalog.phrase("bound.inclusive"), "incluse");
+
+    let err = Catalog::load(&path).unwrap_err();
+    assert!(matches!(err, CatalogError::Io(_)));
+    assert!(std::error::Error::source(&err).is_some());
+}
+
+#[test]
+fn test_braces() {
+    let catalog = Catalog::parse("verdict.false = {{{subject}}} {unknown}!").unwrap();
+    let x = 1;
+    let err = compare_variables!(x > 2).unwrap_err();
+    assert_eq!(
+        err.display_with(&LocalizedStyle::new(&catalog)).to_string(),
+        "{x} !"
+    );
+}
+
+#[test]
+fn test_comparison_set() {
+    let (x, y) = (3, 0.5);
+    let mut set = ComparisonSet::new();
+    set.check(compare_variables!(owned x > 10));
+    set.check(compare_variables!(owned y < 1.0));
+    assert_eq!(
+        set.display_with(&GERMAN).to_string(),
+        "1 von 2 Vergleichen fehlgeschlagen:\n- x muss größer als 10 sein, war aber 3"
+    );
+    assert_eq!(
+        set.display_with(&NaturalLanguageStyle).to_string(),
+        "1 of 2 comparisons failed:\n- x must be greater than 10, but was 3"
+    );
+
+    let empty = ComparisonSet::new();
+    assert_eq!(
+        empty.display_with(&GERMAN).to_string(),
+        "alle 0 Vergleiche bestanden"
+    );
+    let catalog = Catalog::parse("set.passed = {total} checks ok").unwrap();
+    assert_eq!(
+        empty
+            .display_with(&LocalizedStyle::new(&catalog))
+            .to_string(),
+        "0 checks ok"
+    );
+}
+
+#[test]
+fn test_condition() {
+    let (x, allow_zero) = (0, false);
+    let err = compare_variables!(x > 0 || allow_zero).unwrap_err();
+    assert_eq!(
+        err.display_with(&GERMAN).to_string(),
+        "x muss größer als 0 sein, war aber 0 || `allow_zero` ist falsch"
+    );
+
+    let err = compare_variables!(x > 0 && x < 10 && allow_zero).unwrap_err();
+    assert_eq!(
+        err.display_with(&GERMAN).to_string(),
+        "x muss größer als 0 sein, war aber 0 && (2 Operanden nicht ausgewertet)"
+    );
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 0) > 0` is false && (2 operands not evaluated)"
+    );
+}
@@ -0,0 +1,56 @@
+use compare_variables::{
+    Comparison, ComparisonErrorTrait, ComparisonOperator, ComparisonValue, SourceLocation,
+    compare_variables,
+};
+
+fn check(x: i32) -> Result<(), Comparison<i32>> {
+    compare_variables!(x > 0)?;
+    return Ok(());
+}
+
+#[test]
+fn test_macro_location() {
+    let x = 1.5;
+    let err = compare_variables!(x > 2.0).unwrap_err();
+    let location = err.location().expect("location is set by the macro");
+    assert_eq!(location.file, file!());
+    assert_eq!(location.line, line!() - 3);
+    assert_eq!(location.module_path, module_path!());
+    assert_eq!(err.to_string(), "`x (value: 1.5) > 2.0` is false");
+    assert_eq!(
+        format!("{err:#}"),
+        format!(
+            "`x (value: 1.5) > 2.0` is false at {}:{}:{}",
+            file!(),
+            location.line,
+            location.column
+        )
+    );
+
+    // The location is kept when converting into an owned comparison
+    let err = check(-1).unwrap_err();
+    assert_eq!(err.location().unwrap().line, 7);
+
+    let boxed: Box<dyn ComparisonErrorTrait> = Box::new(err);
+    assert_eq!(boxed.location().unwrap().line, 7);
+}
+
+#[test]
+fn test_without_macro() {
+    static LOCATION: SourceLocation = SourceLocation::new("src/config.rs", 12, 8, "app::config");
+
+    let cmp = Comparison::new(
+        ComparisonValue::new(3, Some("x")),
+        [(ComparisonOperator::Lesser, ComparisonValue::new(2, None))],
+    );
+    assert!(cmp.location().is_none());
+    assert_eq!(format!("{cmp:#}"), "`x (value: 3) < 2` is false");
+
+    let cmp = cmp.with_location(&LOCATION);
+    assert_eq!(cmp.location(), Some(&LOCATION));
+    assert_eq!(
+        format!("{cmp:#}"),
+        "`x (value: 3) < 2` is false at src/config.rs:12:8"
+    );
+    assert!(cmp.into_result().is_err());
+}
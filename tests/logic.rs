@@ -0,0 +1,150 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use compare_variables::{
+    ComparisonOutcome, Condition, NaturalLanguageStyle, assert_compare, compare_variables,
+};
+
+#[test]
+fn test_or() {
+    let (x, allow_zero) = (0, true);
+    let condition = compare_variables!(x > 0 || allow_zero).unwrap();
+    assert_eq!(
+        condition.to_string(),
+        "`x (value: 0) > 0` is false || `allow_zero` is true"
+    );
+
+    let x = -1;
+    let err =
+        compare_variables!(x > 0 || x == 0 || allow_zero as zero_allowed && x > -1).unwrap_err();
+    assert_eq!(err.outcome(), ComparisonOutcome::False);
+    assert_eq!(
+        err.to_string(),
+        "`x (value: -1) > 0` is false || `x (value: -1) == 0` is false || \
+         (`zero_allowed` is true && `x (value: -1) > -1` is false)"
+    );
+}
+
+#[test]
+fn test_and() {
+    let (a, b, c, d) = (1, 2, "x", "y");
+    assert!(compare_variables!((a < b) && (c != d)).is_ok());
+
+    let err = compare_variables!(a > b && c != d && a < 10).unwrap_err();
+    let Condition::And(junction) = &err else {
+        panic!()
+    };
+    assert_eq!(junction.operands().len(), 1);
+    assert_eq!(junction.skipped(), 2);
+    assert_eq!(
+        err.to_string(),
+        "`a (value: 1) > b (value: 2)` is false && (2 operands not evaluated)"
+    );
+}
+
+#[test]
+fn test_short_circuit() {
+    let calls = Cell::new(0);
+    let value = || {
+        calls.set(calls.get() + 1);
+        return 5;
+    };
+
+    assert!(compare_variables!(1 < 2 || value() > 10).is_ok());
+    assert_eq!(calls.get(), 0);
+    assert!(compare_variables!(1 > 2 && value() > 10).is_err());
+    assert_eq!(calls.get(), 0);
+    assert!(compare_variables!(1 > 2 || value() < 10).is_ok());
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn test_not() {
+    let x = 1.0;
+    assert!(compare_variables!(!(x > 2.0)).is_ok());
+    assert!(compare_variables!(!(x < 2.0) || !(x == 1.0)).is_err());
+
+    // Incomparable values are neither true nor false
+    let x = f64::NAN;
+    let err = compare_variables!(!(x < 2.0)).unwrap_err();
+    assert_eq!(err.outcome(), ComparisonOutcome::Incomparable);
+    assert_eq!(
+        err.to_string(),
+        "!(`x (value: NaN) < 2.0` is undecidable: values are not comparable)"
+    );
+
+    // `!` in front of an operand is part of the operand
+    let v: Vec<i32> = Vec::new();
+    assert!(compare_variables!(!v.is_empty() || v.len() == 0).is_ok());
+}
+
+#[test]
+fn test_incomparable_or() {
+    let x = f64::NAN;
+    let err = compare_variables!(x < 0.0 || x > 1.0).unwrap_err();
+    assert_eq!(err.outcome(), ComparisonOutcome::Incomparable);
+    let condition = compare_variables!(x < 0.0 || 1 == 1).unwrap();
+    assert!(condition.is_true());
+}
+
+#[test]
+fn test_combined_syntax() {
+    struct Interval {
+        lo: f64,
+        hi: f64,
+    }
+    let interval = Interval { lo: 0.0, hi: 1.0 };
+    let x = 1.0;
+    assert!(
+        compare_variables!(
+            x in interval.lo..interval.hi || x ~= interval.hi, abs = 1e-9 && owned x.to_string() == "1".to_string()
+        )
+        .is_ok()
+    );
+}
+
+#[test]
+fn test_report_and_style() {
+    fn check(name: &str, required: bool) -> Result<(), Condition<'static>> {
+        compare_variables!(name.len() > 0 || !(required)).map_err(|c| c.to_report())?;
+        return Ok(());
+    }
+    let err = check("", true).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`name.len() (value: 0) > 0` is false || !(`required` is true)"
+    );
+    assert_eq!(
+        err.display_with(&NaturalLanguageStyle).to_string(),
+        "name.len() must be greater than 0, but was 0 || !(`required` is true)"
+    );
+}
+
+#[test]
+fn test_non_send_operands() {
+    let (a, b) = (Rc::new(1), Rc::new(1));
+    assert!(compare_variables!(a > b || a == b).is_ok());
+
+    let b = Rc::new(2);
+    let err = compare_variables!(a > b || a == b).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`a (value: 1) > b (value: 2)` is false || `a (value: 1) == b (value: 2)` is false"
+    );
+}
+
+#[test]
+fn test_assertions() {
+    let x = 1;
+    assert_compare!(x > 0 || x < -10);
+    assert_compare!(x > 0 && x < 10, "x is out of range");
+
+    let result = std::panic::catch_unwind(|| {
+        assert_compare!(x > 5 || x < -5, "x = {} is too small", x);
+    });
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(
+        message,
+        "`x (value: 1) > 5` is false || `x (value: 1) < -5` is false: x = 1 is too small"
+    );
+}
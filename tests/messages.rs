@@ -0,0 +1,72 @@
+use compare_variables::{
+    Comparison, ComparisonErrorTrait, ComparisonOperator, ComparisonValue, compare_variables,
+};
+
+const MAX: u32 = 3;
+
+fn connect(retries: u32, host: &str) -> Result<(), Comparison<u32>> {
+    compare_variables!(retries <= MAX, "retry budget exhausted for {}", host)?;
+    return Ok(());
+}
+
+#[test]
+fn test_macro_message() {
+    let err = connect(4, "example.com").unwrap_err();
+    assert_eq!(
+        err.message(),
+        Some("retry budget exhausted for example.com")
+    );
+    assert_eq!(
+        err.to_string(),
+        "`retries (value: 4) <= MAX (value: 3)` is false: retry budget exhausted for example.com"
+    );
+
+    let boxed: Box<dyn ComparisonErrorTrait> = Box::new(err);
+    assert_eq!(
+        boxed.message(),
+        Some("retry budget exhausted for example.com")
+    );
+
+    // Inline format arguments, approximate equality and ranges
+    let x = 0.5;
+    let err = compare_variables!(x ~= 0.6, abs = 1e-3, "x = {x} is off").unwrap_err();
+    assert_eq!(err.message(), Some("x = 0.5 is off"));
+    let err = compare_variables!(x in 1.0.., "x too small").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 0.5) in 1.0..` is false: x too small"
+    );
+}
+
+#[test]
+fn test_message_is_lazy() {
+    fn expensive(counter: &mut u32) -> u32 {
+        *counter += 1;
+        return *counter;
+    }
+
+    let mut counter = 0;
+    let ok = compare_variables!(1 < 2, "{}", expensive(&mut counter)).unwrap();
+    assert_eq!(counter, 0);
+    assert_eq!(ok.message(), None);
+
+    let err = compare_variables!(2 < 1, "{}", expensive(&mut counter)).unwrap_err();
+    assert_eq!(counter, 1);
+    assert_eq!(err.message(), Some("1"));
+}
+
+#[test]
+fn test_without_macro() {
+    let cmp = Comparison::new(
+        ComparisonValue::new(1, Some("x")),
+        [(ComparisonOperator::Greater, ComparisonValue::new(2, None))],
+    );
+    assert_eq!(cmp.message(), None);
+
+    let cmp = cmp.with_message(String::from("x must exceed the threshold"));
+    assert_eq!(cmp.message(), Some("x must exceed the threshold"));
+    assert_eq!(
+        cmp.to_string(),
+        "`x (value: 1) > 2` is false: x must exceed the threshold"
+    );
+}
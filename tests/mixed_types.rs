@@ -0,0 +1,72 @@
+use std::ffi::{OsStr, OsString};
+
+use compare_variables::{Comparison, ComparisonOperator, ComparisonValue, compare_variables};
+
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+struct Meters(f64);
+
+impl PartialEq<f64> for Meters {
+    fn eq(&self, other: &f64) -> bool {
+        return self.0 == *other;
+    }
+}
+
+impl PartialOrd<f64> for Meters {
+    fn partial_cmp(&self, other: &f64) -> Option<std::cmp::Ordering> {
+        return self.0.partial_cmp(other);
+    }
+}
+
+#[test]
+fn test_wrapper_and_float() {
+    let length = Meters(2.0);
+    let max = 1.5f64;
+    assert!(compare_variables!(length > max).is_ok());
+    assert!(compare_variables!(length > 1.5).is_ok());
+    assert!(compare_variables!(length == Meters(2.0)).is_ok());
+
+    let err = compare_variables!(length <= max).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`length (value: Meters(2.0)) <= max (value: 1.5)` is false"
+    );
+    assert_eq!(*err.first_val().value, Meters(2.0));
+    assert_eq!(*err.other_vals()[0].value, 1.5);
+
+    // All values after the first one share a type
+    let min = 0.5f64;
+    let err = compare_variables!(length < max < min).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`length (value: Meters(2.0)) < max (value: 1.5) < min (value: 0.5)` is false \
+        (failed: `length (value: Meters(2.0)) < max (value: 1.5)`, `max (value: 1.5) < min (value: 0.5)`)"
+    );
+}
+
+#[test]
+fn test_strings() {
+    let name = OsString::from("beta");
+    let first = OsStr::new("alpha");
+    let err = compare_variables!(name == first).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`name (value: \"beta\") == first (value: \"alpha\")` is false"
+    );
+    assert_eq!(
+        err.link_ordering(0),
+        Some(Some(std::cmp::Ordering::Greater))
+    );
+}
+
+#[test]
+fn test_without_macro() {
+    let cmp: Comparison<Meters, f64> = Comparison::new(
+        ComparisonValue::new(Meters(1.0), Some("x")),
+        [(ComparisonOperator::Equal, ComparisonValue::new(1.0, None))],
+    );
+    assert!(cmp.is_true());
+    assert_eq!(cmp.len(), 2);
+
+    assert!(ComparisonOperator::Greater.is_true(&Meters(2.0), &1.0));
+    assert!(ComparisonOperator::Equal.is_true(OsStr::new("abc"), &OsString::from("abc")));
+}
@@ -0,0 +1,83 @@
+use compare_variables::{Comparison, compare_variables};
+
+#[test]
+fn test_operands_are_borrowed() {
+    let name = String::from("Bob");
+    let empty = String::new();
+    assert!(compare_variables!(name != empty).is_ok());
+    assert!(compare_variables!(name > empty).is_ok());
+    assert_eq!(name, "Bob");
+
+    let v = vec![3, 2, 1];
+    let w = vec![1, 2, 3];
+    let err = compare_variables!(v < w).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`v (value: [3, 2, 1]) < w (value: [1, 2, 3])` is false"
+    );
+    assert_eq!(v.len(), 3);
+
+    let lo = String::from("a");
+    let hi = String::from("c");
+    assert!(compare_variables!(name in lo..hi).is_err());
+    let range = String::from("A")..String::from("C");
+    assert!(compare_variables!(name in range).is_ok());
+    assert_eq!(range.start, "A");
+}
+
+#[test]
+fn test_owned_mode() {
+    let err: Comparison<String> = {
+        let name = String::from("Bob");
+        let other = String::from("Alice");
+        compare_variables!(owned name < other).unwrap_err()
+    };
+    assert_eq!(
+        err.to_string(),
+        "`name (value: \"Bob\") < other (value: \"Alice\")` is false"
+    );
+
+    let x = 2;
+    let err: Comparison<i32> = compare_variables!(owned x in 0..=1).unwrap_err();
+    assert_eq!(err.to_string(), "`x (value: 2) in 0..=1` is false");
+
+    let y = 0.5;
+    let err: Comparison<f64> =
+        compare_variables!(owned (y * 2.0) as z ~= 0.0, abs = 0.1).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`z (value: 1.0) ~= 0.0` is false (absolute deviation: 1.0, tolerance: 0.1)"
+    );
+
+    // Without a following operand, "owned" is a variable name
+    let owned = 1;
+    assert!(compare_variables!(owned > 0).is_ok());
+    let err: Comparison<&i32> = compare_variables!(owned < 0).unwrap_err();
+    assert_eq!(err.to_string(), "`owned (value: 1) < 0` is false");
+    assert!(compare_variables!(owned.pow(2) == 1).is_ok());
+}
+
+#[test]
+fn test_into_owned() {
+    fn check(names: &[String]) -> Result<(), Comparison<usize>> {
+        let count = names.len();
+        compare_variables!(count > 1)?;
+        return Ok(());
+    }
+    assert_eq!(
+        check(&[]).unwrap_err().to_string(),
+        "`count (value: 0) > 1` is false"
+    );
+
+    let err = {
+        let v = [String::from("a")];
+        compare_variables!(v[0] == "b".to_string())
+            .unwrap_err()
+            .into_owned()
+    };
+    assert_eq!(
+        err.to_string(),
+        "`v[0] (value: \"a\") == \"b\".to_string() (value: \"b\")` is false"
+    );
+    assert_eq!(err.first_val().value, "a");
+}
@@ -0,0 +1,98 @@
+use compare_variables::compare_variables;
+
+const VALID: std::ops::RangeInclusive<i32> = 0..=1;
+
+#[test]
+fn test_range_literals() {
+    let x = 2;
+    assert!(compare_variables!(x in 0..=2).is_ok());
+    assert!(compare_variables!(x in 0..3).is_ok());
+    assert!(compare_variables!(x in ..3).is_ok());
+    assert!(compare_variables!(x in ..=2).is_ok());
+    assert!(compare_variables!(x in 2..).is_ok());
+
+    let err = compare_variables!(x in 0..=1).unwrap_err();
+    assert_eq!(err.to_string(), "`x (value: 2) in 0..=1` is false");
+
+    let err = compare_variables!(x in 0..2).unwrap_err();
+    assert_eq!(err.to_string(), "`x (value: 2) in 0..2` is false");
+
+    let err = compare_variables!(x in ..2).unwrap_err();
+    assert_eq!(err.to_string(), "`x (value: 2) in ..2` is false");
+
+    let err = compare_variables!(x in 5..).unwrap_err();
+    assert_eq!(err.to_string(), "`x (value: 2) in 5..` is false");
+
+    let y = 0.5;
+    assert!(compare_variables!(y in 0.0..=1.0).is_ok());
+    let err = compare_variables!(owned y * 4.0 in 0.0..=1.0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`y * 4.0 (value: 2.0) in 0.0..=1.0` is false"
+    );
+}
+
+#[test]
+fn test_range_bound_variables() {
+    let v = [1, 2, 3];
+    let lo = 1;
+    let hi = 2;
+    let err = compare_variables!(v[2] as third in lo..=hi).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`third (value: 3) in lo (value: 1)..=hi (value: 2)` is false"
+    );
+
+    let err = compare_variables!(owned v.len() in lo + 3..v.len()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`v.len() (value: 3) in lo + 3 (value: 4)..v.len() (value: 3)` is false"
+    );
+    assert_eq!(err.range_element(), Some(1));
+    assert_eq!(err.failed_links().collect::<Vec<_>>(), vec![0, 1]);
+}
+
+#[test]
+fn test_range_values() {
+    let x = 2;
+    let err = compare_variables!(x in VALID).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 2) in VALID (value: 0..=1)` is false"
+    );
+    assert!(compare_variables!(x in VALID as allowed).is_err());
+    let err = compare_variables!(x in VALID as allowed).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 2) in allowed (value: 0..=1)` is false"
+    );
+
+    let range = 0..10;
+    assert!(compare_variables!(x in range).is_ok());
+
+    let ranges = [1..2, 2..3];
+    assert!(compare_variables!(x in ranges[1]).is_ok());
+    let err = compare_variables!(x in ranges[0]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`x (value: 2) in ranges[0] (value: 1..2)` is false"
+    );
+
+    let bounds = (std::ops::Bound::Excluded(2), std::ops::Bound::Included(3));
+    let err = compare_variables!(x in bounds).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`2 < x (value: 2) <= 3` is false (failed: `2 < x (value: 2)`)"
+    );
+}
+
+#[test]
+fn test_range_nan() {
+    let x = f64::NAN;
+    let err = compare_variables!(x in 0.0..1.0).unwrap_err();
+    assert!(err.is_incomparable());
+    assert_eq!(
+        err.to_string(),
+        "`x (value: NaN) in 0.0..1.0` is undecidable: values are not comparable"
+    );
+}
@@ -0,0 +1,88 @@
+use std::cell::Cell;
+
+use compare_variables::{
+    ComparisonErrorTrait, ComparisonOutcome, ComparisonReport, NaturalLanguageStyle,
+    compare_variables,
+};
+
+fn assert_storable<T: Clone + Send + Sync + 'static>(_: &T) {}
+
+#[test]
+fn test_borrowed_values() {
+    let report = {
+        let values = vec![1, 2, 3];
+        let expected = vec![1, 2];
+        compare_variables!(values == expected, "unexpected values")
+            .unwrap_err()
+            .to_report()
+    };
+    assert_storable(&report);
+    assert_eq!(
+        report.to_string(),
+        "`values (value: [1, 2, 3]) == expected (value: [1, 2])` is false: unexpected values"
+    );
+    assert_eq!(report.message(), Some("unexpected values"));
+    assert_eq!(
+        report.value(0).unwrap().variable_name.as_deref(),
+        Some("values")
+    );
+    assert_eq!(report.value(1).unwrap().value.as_str(), "[1, 2]");
+    assert_eq!(report.outcome(), ComparisonOutcome::False);
+    assert!(report.location().is_some());
+}
+
+#[test]
+fn test_non_send_values() {
+    #[derive(Debug, PartialEq, PartialOrd)]
+    struct Counter(Cell<u32>);
+
+    let counter = Counter(Cell::new(5));
+    let limit = Counter(Cell::new(3));
+    let report = compare_variables!(counter <= limit)
+        .unwrap_err()
+        .to_report();
+    let report = std::thread::spawn(move || report.clone()).join().unwrap();
+    assert_eq!(
+        report.to_string(),
+        "`counter (value: Counter(Cell { value: 5 })) <= limit (value: Counter(Cell { value: 3 }))` is false"
+    );
+}
+
+#[test]
+fn test_special_comparisons() {
+    let x = 0.5;
+    let err = compare_variables!(x ~= 0.75, abs = 0.125).unwrap_err();
+    assert_eq!(err.to_report().to_string(), err.to_string());
+
+    let err = compare_variables!(x in 1.0..=2.0).unwrap_err();
+    let report = err.to_report();
+    assert_eq!(report.to_string(), "`x (value: 0.5) in 1.0..=2.0` is false");
+    assert_eq!(report.range_element(), Some(1));
+    assert_eq!(
+        report.display_with(&NaturalLanguageStyle).to_string(),
+        "x must be between 1.0 and 2.0 (inclusive), but was 0.5"
+    );
+}
+
+#[test]
+fn test_trait_object() {
+    let x = 3;
+    let err: Box<dyn ComparisonErrorTrait> =
+        Box::new(compare_variables!(owned x > 10).unwrap_err());
+    let report: ComparisonReport = err.to_report();
+    assert_eq!(report.to_string(), err.to_string());
+
+    // Reports can be used as trait objects themselves
+    let boxed: Box<dyn ComparisonErrorTrait> = Box::new(report);
+    assert_eq!(boxed.to_string(), "`x (value: 3) > 10` is false");
+}
+
+#[test]
+fn test_serialization() {
+    let x = 3u8;
+    let report = compare_variables!(x > 10).unwrap_err().to_report();
+    let json = serde_json::to_value(&report).unwrap();
+    assert_eq!(json["values"][0]["value"], serde_json::json!("3"));
+    let restored: ComparisonReport = serde_json::from_value(json).unwrap();
+    assert_eq!(restored.to_string(), report.to_string());
+}
@@ -0,0 +1,161 @@
+use std::borrow::Cow;
+
+use compare_variables::{
+    Comparison, ComparisonOperator, ComparisonOutcome, ComparisonValue, compare_variables,
+};
+use serde_json::{Value, json};
+
+#[test]
+fn test_operator() {
+    assert_eq!(
+        serde_json::to_value(ComparisonOperator::LesserOrEqual).unwrap(),
+        json!("<=")
+    );
+    let operator: ComparisonOperator = serde_json::from_value(json!("!=")).unwrap();
+    assert_eq!(operator, ComparisonOperator::Inequal);
+    assert!(serde_json::from_value::<ComparisonOperator>(json!("~=")).is_err());
+    assert!(serde_json::from_value::<ComparisonOperator>(json!("=>")).is_err());
+}
+
+#[test]
+fn test_value() {
+    let value = ComparisonValue::new(1.5, Some("x"));
+    assert_eq!(
+        serde_json::to_value(&value).unwrap(),
+        json!({"value": 1.5, "variable_name": "x"})
+    );
+    let value: ComparisonValue<i32> =
+        serde_json::from_value(json!({"value": 2, "variable_name": null})).unwrap();
+    assert_eq!(value.value, 2);
+    assert_eq!(value.variable_name, None);
+
+    // Deserialized names are owned by the value
+    let value: ComparisonValue<i32> =
+        serde_json::from_value(json!({"value": 2, "variable_name": "runtime"})).unwrap();
+    assert!(matches!(value.variable_name, Some(Cow::Owned(_))));
+    assert_eq!(value.to_string(), "runtime (value: 2)");
+}
+
+#[test]
+fn test_comparison_shape() {
+    let x = 3;
+    let err = compare_variables!(owned x > 10, "not enough").unwrap_err();
+    let mut json = serde_json::to_value(&err).unwrap();
+    let location = json.as_object_mut().unwrap().remove("location").unwrap();
+    assert_eq!(location["file"], json!(file!()));
+    assert_eq!(location["line"], json!(line!() - 4));
+    assert_eq!(
+        json,
+        json!({
+            "values": [
+                {"value": 3, "variable_name": "x"},
+                {"value": 10, "variable_name": null}
+            ],
+            "links": [
+                {"operator": ">", "outcome": "false", "ordering": "less"}
+            ],
+            "outcome": "false",
+            "range": null,
+            "message": "not enough"
+        })
+    );
+
+    // Borrowed values serialize like owned ones
+    let err = compare_variables!(x > 10).unwrap_err();
+    assert_eq!(
+        serde_json::to_value(&err).unwrap()["values"][0]["value"],
+        json!(3)
+    );
+}
+
+#[test]
+fn test_special_comparisons() {
+    let x = 0.5;
+    let err = compare_variables!(owned x ~= 0.75, abs = 0.125).unwrap_err();
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(
+        json["links"],
+        json!([{
+            "operator": "~=",
+            "outcome": "false",
+            "ordering": "less",
+            "tolerance": {"absolute": 0.125},
+            "deviation": 0.25
+        }])
+    );
+
+    let x = f64::NAN;
+    let err = compare_variables!(owned x in 0.0..1.0).unwrap_err();
+    let json = serde_json::to_value(&err).unwrap();
+    assert_eq!(json["outcome"], json!("incomparable"));
+    assert_eq!(json["links"][0]["ordering"], Value::Null);
+    assert_eq!(json["range"], json!({"element": 1, "name": null}));
+}
+
+#[test]
+fn test_roundtrip() {
+    let x = 0.5;
+    let comparisons: [Comparison<f64>; 3] = [
+        compare_variables!(owned 0.0 < x <= 0.25, "x is too large").unwrap_err(),
+        compare_variables!(owned x ~= 0.5, rel = 1e-9).unwrap(),
+        compare_variables!(owned x in ..1.0).unwrap(),
+    ];
+    for comparison in comparisons {
+        let json = serde_json::to_string(&comparison).unwrap();
+        let restored: Comparison<f64> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.to_string(), comparison.to_string());
+        assert_eq!(restored.outcome(), comparison.outcome());
+        assert_eq!(restored.operators(), comparison.operators());
+        assert_eq!(restored.range_element(), comparison.range_element());
+        assert!(restored.location().is_none());
+    }
+}
+
+#[test]
+fn test_invalid_comparison() {
+    let values = json!([{"value": 1, "variable_name": "a"}, {"value": 2, "variable_name": null}]);
+    let link = json!({"operator": "<", "outcome": "true", "ordering": "less"});
+
+    let valid = json!({"values": values, "links": [link]});
+    let cmp: Comparison<i32> = serde_json::from_value(valid).unwrap();
+    assert_eq!(cmp.outcome(), ComparisonOutcome::True);
+    assert_eq!(cmp.to_string(), "`a (value: 1) < 2` is true");
+
+    let too_many_links = json!({"values": values, "links": [link, link]});
+    let err = serde_json::from_value::<Comparison<i32>>(too_many_links).unwrap_err();
+    assert_eq!(err.to_string(), "expected 1 links for 2 values, found 2");
+
+    let no_values = json!({"values": [], "links": []});
+    assert!(serde_json::from_value::<Comparison<i32>>(no_values).is_err());
+
+    let approx_without_tolerance = json!({
+        "values": values,
+        "links": [{"operator": "~=", "outcome": "true", "ordering": "less"}]
+    });
+    assert!(serde_json::from_value::<Comparison<i32>>(approx_without_tolerance).is_err());
+
+    let invalid_range = json!({"values": values, "links": [link], "range": {"element": 2}});
+    assert!(serde_json::from_value::<Comparison<i32>>(invalid_range).is_err());
+
+    let contradicting_outcome = json!({
+        "values": [{"value": 3, "variable_name": null}, {"value": 10, "variable_name": null}],
+        "links": [{"operator": ">", "outcome": "true", "ordering": "less"}]
+    });
+    let err = serde_json::from_value::<Comparison<i32>>(contradicting_outcome).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "outcome of link 0 contradicts its ordering or deviation"
+    );
+
+    let contradicting_deviation = json!({
+        "values": [{"value": 0.5, "variable_name": null}, {"value": 0.75, "variable_name": null}],
+        "links": [{
+            "operator": "~=",
+            "outcome": "true",
+            "ordering": "less",
+            "tolerance": {"absolute": 0.125},
+            "deviation": 0.25
+        }]
+    });
+    assert!(serde_json::from_value::<Comparison<f64>>(contradicting_deviation).is_err());
+}
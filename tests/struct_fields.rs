@@ -20,14 +20,14 @@ impl NamedFields {
         &self,
         value: f64,
     ) -> Result<compare_variables::Comparison<f64>, compare_variables::Comparison<f64>> {
-        return compare_variables!(self.field1 < value);
+        return compare_variables!(owned self.field1 < value);
     }
 
     fn value_bigger_than_field2(
         &self,
         value: f64,
     ) -> Result<compare_variables::Comparison<f64>, compare_variables::Comparison<f64>> {
-        return compare_variables!(self.field2 as field2 < value);
+        return compare_variables!(owned self.field2 as field2 < value);
     }
 }
 
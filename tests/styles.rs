@@ -0,0 +1,174 @@
+use std::fmt::{Formatter, Result};
+
+use compare_variables::{
+    Comparison, ComparisonOperator, ComparisonOutcome, ComparisonValue, ComparisonView,
+    ExpressionStyle, German, LocalizedStyle, MessageStyle, NaturalLanguageStyle, compare_variables,
+};
+
+fn natural<L, R>(comparison: &Comparison<L, R>) -> String
+where
+    L: PartialOrd<R> + std::fmt::Debug,
+    R: std::fmt::Debug,
+{
+    return comparison.display_with(&NaturalLanguageStyle).to_string();
+}
+
+#[test]
+fn test_two_values() {
+    let x = 3;
+    let limit = 10;
+    let cmp = compare_variables!(x > 10).unwrap_err();
+    assert_eq!(natural(&cmp), "x must be greater than 10, but was 3");
+    let cmp = compare_variables!(x >= limit).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be greater than or equal to limit (10), but was 3"
+    );
+    let cmp = compare_variables!(x == 4).unwrap_err();
+    assert_eq!(natural(&cmp), "x must be equal to 4, but was 3");
+    let cmp = compare_variables!(x != 3).unwrap_err();
+    assert_eq!(natural(&cmp), "x must be different from 3, but was 3");
+
+    // The named value is the subject of the statement
+    let cmp = compare_variables!(10 < x).unwrap_err();
+    assert_eq!(natural(&cmp), "x must be greater than 10, but was 3");
+
+    // Without variable names
+    let cmp = compare_variables!(2 < 1).unwrap_err();
+    assert_eq!(natural(&cmp), "2 is not less than 1");
+    let cmp = compare_variables!(1 < 2).unwrap();
+    assert_eq!(natural(&cmp), "1 is less than 2");
+
+    let cmp = compare_variables!(x < limit).unwrap();
+    assert_eq!(natural(&cmp), "x is 3, which is less than limit (10)");
+}
+
+#[test]
+fn test_between() {
+    let x = 2.0;
+    let cmp = compare_variables!(0.0 < x < 1.0).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be between 0.0 and 1.0 (exclusive), but was 2.0"
+    );
+    let cmp = compare_variables!(0.0 <= x <= 1.0).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be between 0.0 and 1.0 (inclusive), but was 2.0"
+    );
+    let cmp = compare_variables!(0.0 <= x < 1.0).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be between 0.0 (inclusive) and 1.0 (exclusive), but was 2.0"
+    );
+    let cmp = compare_variables!(1.0 > x >= 0.0).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be between 0.0 (inclusive) and 1.0 (exclusive), but was 2.0"
+    );
+    let cmp = compare_variables!(0.0 < x < 3.0).unwrap();
+    assert_eq!(
+        natural(&cmp),
+        "x is 2.0, which is between 0.0 and 3.0 (exclusive)"
+    );
+}
+
+#[test]
+fn test_ranges() {
+    let x = 2;
+    let cmp = compare_variables!(x in 0..2).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be between 0 (inclusive) and 2 (exclusive), but was 2"
+    );
+    let cmp = compare_variables!(x in ..=1).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be less than or equal to 1, but was 2"
+    );
+    let cmp = compare_variables!(x in 5..).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be greater than or equal to 5, but was 2"
+    );
+}
+
+#[test]
+fn test_chains_and_special_cases() {
+    let (min, low, high, max) = (0.0, 0.5, 2.0, 1.0);
+    let cmp = compare_variables!(min <= low <= high <= max).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "high must be less than or equal to max (1.0), but was 2.0"
+    );
+
+    let x = f64::NAN;
+    let cmp = compare_variables!(x > 0.0).unwrap_err();
+    assert_eq!(cmp.outcome(), ComparisonOutcome::Incomparable);
+    assert_eq!(
+        natural(&cmp),
+        "x must be greater than 0.0, but was NaN (values are not comparable)"
+    );
+
+    let x = 0.5;
+    let cmp = compare_variables!(x ~= 0.6, abs = 1e-3).unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be approximately equal to 0.6 (absolute tolerance: 0.001), but was 0.5"
+    );
+
+    // The custom message is kept
+    let cmp = compare_variables!(x > 1.0, "x is too small").unwrap_err();
+    assert_eq!(
+        natural(&cmp),
+        "x must be greater than 1.0, but was 0.5: x is too small"
+    );
+}
+
+#[test]
+fn test_without_links() {
+    let cmp = Comparison::<i32>::new(ComparisonValue::new(3, Some("x")), []);
+    assert!(cmp.is_true());
+    assert_eq!(natural(&cmp), "x is 3, which is not compared to anything");
+    assert_eq!(
+        cmp.display_with(&LocalizedStyle::new(&German)).to_string(),
+        "x ist 3 und wird mit nichts verglichen"
+    );
+
+    let cmp = Comparison::<i32>::new(ComparisonValue::new(3, None), []);
+    assert_eq!(natural(&cmp), "3 is not compared to anything");
+}
+
+struct Symbols;
+
+impl MessageStyle for Symbols {
+    fn fmt(&self, comparison: &ComparisonView<'_>, f: &mut Formatter<'_>) -> Result {
+        for index in 0..comparison.len() {
+            write!(f, "{:?}", comparison.value(index).unwrap())?;
+            if let Some(operator) = comparison.operator(index) {
+                write!(f, " {operator} ")?;
+            }
+        }
+        if comparison.outcome() != ComparisonOutcome::True {
+            write!(f, " ✗")?;
+        }
+        return Ok(());
+    }
+}
+
+#[test]
+fn test_custom_style() {
+    let cmp = Comparison::new(
+        ComparisonValue::new(1, Some("x")),
+        [
+            (ComparisonOperator::Lesser, ComparisonValue::new(2, None)),
+            (ComparisonOperator::Lesser, ComparisonValue::new(0, None)),
+        ],
+    );
+    assert_eq!(cmp.display_with(&Symbols).to_string(), "1 < 2 < 0 ✗");
+    assert_eq!(cmp.view().failed_links().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(
+        cmp.display_with(&ExpressionStyle).to_string(),
+        cmp.to_string()
+    );
+}
@@ -20,7 +20,24 @@ fn test_compare_variables_float() {
         let err = compare_variables!(zero < arg as alternative_arg <= one).unwrap_err();
         assert_eq!(
             format!("{}", err),
-            "`zero (value: 0.0 m^1) < alternative_arg (value: 2.0 m^1) <= one (value: 1.0 m^1)` is false"
+            "`zero (value: 0.0 m^1) < alternative_arg (value: 2.0 m^1) <= one (value: 1.0 m^1)` is false \
+            (failed: `alternative_arg (value: 2.0 m^1) <= one (value: 1.0 m^1)`)"
         );
     }
 }
+
+#[test]
+fn test_compare_variables_approx() {
+    let arg = Length::new::<meter>(1.0 + 1e-12);
+    let one = Length::new::<meter>(1.0);
+    assert!(compare_variables!(arg ~= one, abs = 1e-9).is_ok());
+    assert!(compare_variables!(arg ~= one, rel = 1e-9).is_ok());
+
+    let arg = Length::new::<meter>(1.5);
+    let err = compare_variables!(arg ~= one, abs = 0.1).unwrap_err();
+    assert_eq!(
+        format!("{}", err),
+        "`arg (value: 1.5 m^1) ~= one (value: 1.0 m^1)` is false \
+        (absolute deviation: 0.5, tolerance: 0.1)"
+    );
+}
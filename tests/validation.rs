@@ -0,0 +1,87 @@
+use compare_variables::{AnyComparison, Comparison, ComparisonSet, compare_variables, validate};
+
+struct Config {
+    threads: usize,
+    ratio: f64,
+    name: String,
+    default_name: String,
+}
+
+impl Config {
+    fn validate(&self) -> Result<(), ComparisonSet> {
+        return validate! {
+            self.threads > 0,
+            0.0 <= self.ratio <= 1.0,
+            self.ratio ~= 0.5, abs = 0.25,
+            self.name != self.default_name,
+        };
+    }
+}
+
+#[test]
+fn test_validate_macro() {
+    let mut config = Config {
+        threads: 4,
+        ratio: 0.5,
+        name: String::from("custom"),
+        default_name: String::from("default"),
+    };
+    assert!(config.validate().is_ok());
+
+    config.threads = 0;
+    config.ratio = 1.5;
+    let err = config.validate().unwrap_err();
+    assert_eq!(err.len(), 4);
+    assert_eq!(err.passed(), 1);
+    assert_eq!(err.failed(), 3);
+    assert!(err.failures()[0].is::<Comparison<usize>>());
+    assert_eq!(err.failures()[1].variable_name(1), Some("self.ratio"));
+    assert_eq!(
+        err.to_string(),
+        "3 of 4 comparisons failed:\n\
+         - `self.threads (value: 0) > 0` is false\n\
+         - `0.0 <= self.ratio (value: 1.5) <= 1.0` is false (failed: `self.ratio (value: 1.5) <= 1.0`)\n\
+         - `self.ratio (value: 1.5) ~= 0.5` is false (absolute deviation: 1.0, tolerance: 0.25)"
+    );
+
+    // Empty lists and owned comparisons
+    assert!(validate!().is_ok());
+    let x = 3;
+    let err = validate!(owned x.to_string() == String::from("4")).unwrap_err();
+    assert!(err.failures()[0].is::<Comparison<String>>());
+}
+
+#[test]
+fn test_collect_manually() {
+    let mut set = ComparisonSet::new();
+    assert!(set.is_empty());
+    assert_eq!(set.to_string(), "all 0 comparisons passed");
+
+    let (a, b) = (1, 2.0);
+    assert!(set.check(compare_variables!(a < 2)));
+    assert!(!set.check(compare_variables!(b > 3.0)));
+    assert!(set.push(compare_variables!(a == 1).unwrap()));
+    set.extend([compare_variables!(a > 5).unwrap_err()]);
+    assert_eq!(set.len(), 4);
+    assert_eq!(set.passed(), 2);
+    assert!(!set.is_ok());
+
+    let names: Vec<_> = set.iter().map(|c| c.variable_name(0)).collect();
+    assert_eq!(names, [Some("b"), Some("a")]);
+
+    let failures: Vec<AnyComparison> = set.into_iter().collect();
+    assert_eq!(failures.len(), 2);
+}
+
+#[test]
+fn test_alternate_format() {
+    let x = 1;
+    let err = validate!(x > 1).unwrap_err();
+    let message = format!("{err:#}");
+    assert!(message.starts_with(
+        "1 of 1 comparisons failed:\n- `x (value: 1) > 1` is false at tests/validation.rs:"
+    ));
+
+    let err: Box<dyn std::error::Error> = Box::new(err);
+    assert!(err.to_string().ends_with("`x (value: 1) > 1` is false"));
+}
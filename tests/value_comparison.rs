@@ -64,7 +64,10 @@ fn test_compare_variables_raw_value() {
     {
         let arg = 1usize;
         let err = compare_variables!(0 > val arg > 2).unwrap_err();
-        assert_eq!(err.to_string(), "`0 > 1 > 2` is false");
+        assert_eq!(
+            err.to_string(),
+            "`0 > 1 > 2` is false (failed: `0 > 1`, `1 > 2`)"
+        );
     }
     {
         let arg = 1usize;
@@ -91,10 +94,10 @@ fn test_compare_variables_raw_value() {
 fn check_arg_message() {
     let message = Comparison::new_checked(
         ComparisonValue::new(1.0, None),
-        ComparisonOperator::Equal,
-        ComparisonValue::new(2.0, Some("argument")),
-        ComparisonOperator::Equal,
-        None,
+        [(
+            ComparisonOperator::Equal,
+            ComparisonValue::new(2.0, Some("argument")),
+        )],
     )
     .unwrap_err();
     assert_eq!(
@@ -104,57 +107,236 @@ fn check_arg_message() {
 
     let message = Comparison::new_checked(
         ComparisonValue::new(2.0, None),
-        ComparisonOperator::Equal,
-        ComparisonValue::new(2.0, Some("argument")),
-        ComparisonOperator::Greater,
-        Some(ComparisonValue::new(2.0, Some("argument"))),
+        [
+            (
+                ComparisonOperator::Equal,
+                ComparisonValue::new(2.0, Some("argument")),
+            ),
+            (
+                ComparisonOperator::Greater,
+                ComparisonValue::new(2.0, Some("argument")),
+            ),
+        ],
     )
     .unwrap_err();
     assert_eq!(
         format!("{message}"),
-        "`2.0 == argument (value: 2.0) > argument (value: 2.0)` is false"
+        "`2.0 == argument (value: 2.0) > argument (value: 2.0)` is false \
+        (failed: `argument (value: 2.0) > argument (value: 2.0)`)"
     );
 
     let message = Comparison::new_checked(
         ComparisonValue::new(2.0, None),
-        ComparisonOperator::Equal,
-        ComparisonValue::new(2.0, Some("argument")),
-        ComparisonOperator::Greater,
-        Some(ComparisonValue::new(2.0, None)),
+        [
+            (
+                ComparisonOperator::Equal,
+                ComparisonValue::new(2.0, Some("argument")),
+            ),
+            (ComparisonOperator::Greater, ComparisonValue::new(2.0, None)),
+        ],
     )
     .unwrap_err();
     assert_eq!(
         format!("{message}"),
-        "`2.0 == argument (value: 2.0) > 2.0` is false"
+        "`2.0 == argument (value: 2.0) > 2.0` is false (failed: `argument (value: 2.0) > 2.0`)"
     );
 
     let message = Comparison::new_checked(
         ComparisonValue::new(2.0, None),
-        ComparisonOperator::Lesser,
-        ComparisonValue::new(2.0, None),
-        ComparisonOperator::Greater,
-        Some(ComparisonValue::new(2.0, None)),
+        [
+            (ComparisonOperator::Lesser, ComparisonValue::new(2.0, None)),
+            (ComparisonOperator::Greater, ComparisonValue::new(2.0, None)),
+        ],
     )
     .unwrap_err();
-    assert_eq!(format!("{message}"), "`2.0 < 2.0 > 2.0` is false");
+    assert_eq!(
+        format!("{message}"),
+        "`2.0 < 2.0 > 2.0` is false (failed: `2.0 < 2.0`, `2.0 > 2.0`)"
+    );
 
     let message = Comparison::new_checked(
         ComparisonValue::new(0.0, None),
-        ComparisonOperator::Lesser,
-        ComparisonValue::new(2.0, None),
-        ComparisonOperator::LesserOrEqual,
-        Some(ComparisonValue::new(1.0, None)),
+        [
+            (ComparisonOperator::Lesser, ComparisonValue::new(2.0, None)),
+            (
+                ComparisonOperator::LesserOrEqual,
+                ComparisonValue::new(1.0, None),
+            ),
+        ],
     )
     .unwrap_err();
-    assert_eq!(format!("{message}"), "`0.0 < 2.0 <= 1.0` is false");
+    assert_eq!(
+        format!("{message}"),
+        "`0.0 < 2.0 <= 1.0` is false (failed: `2.0 <= 1.0`)"
+    );
 
     let message = Comparison::new_checked(
         ComparisonValue::new(2.0, None),
-        ComparisonOperator::LesserOrEqual,
-        ComparisonValue::new(2.0, None),
-        ComparisonOperator::GreaterOrEqual,
-        Some(ComparisonValue::new(2.0, None)),
+        [
+            (
+                ComparisonOperator::LesserOrEqual,
+                ComparisonValue::new(2.0, None),
+            ),
+            (
+                ComparisonOperator::GreaterOrEqual,
+                ComparisonValue::new(2.0, None),
+            ),
+        ],
     )
     .unwrap();
     assert_eq!(format!("{message}"), "`2.0 <= 2.0 >= 2.0` is true");
 }
+
+#[test]
+fn test_long_chains() {
+    let (min, low, nominal, high, max) = (0, 1, 2, 3, 4);
+    let ok = compare_variables!(min <= low <= nominal <= high <= max).unwrap();
+    assert_eq!(ok.len(), 5);
+    assert_eq!(ok.operators().len(), 4);
+    assert_eq!(
+        ok.value(2).unwrap().variable_name.as_deref(),
+        Some("nominal")
+    );
+    assert!(ok.value(5).is_none());
+
+    let high = 5;
+    let err = compare_variables!(min <= low <= nominal <= high <= max).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`min (value: 0) <= low (value: 1) <= nominal (value: 2) <= high (value: 5) <= max (value: 4)` is false \
+        (failed: `high (value: 5) <= max (value: 4)`)"
+    );
+
+    let err = compare_variables!(1 < 2 < 3 < 4 < 5 < 6 < 7 < 7).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "`1 < 2 < 3 < 4 < 5 < 6 < 7 < 7` is false (failed: `7 < 7`)"
+    );
+}
+
+#[test]
+fn test_failed_links() {
+    let too_small = -1.0;
+    let too_large = 2.0;
+    let inside = 0.5;
+
+    let err = compare_variables!(0.0 < too_small <= 1.0).unwrap_err();
+    assert_eq!(err.failed_links().collect::<Vec<_>>(), vec![0]);
+    assert_eq!(err.link_is_true(0), Some(false));
+    assert_eq!(err.link_is_true(1), Some(true));
+    assert_eq!(
+        err.to_string(),
+        "`0.0 < too_small (value: -1.0) <= 1.0` is false (failed: `0.0 < too_small (value: -1.0)`)"
+    );
+
+    let err = compare_variables!(0.0 < too_large <= 1.0).unwrap_err();
+    assert_eq!(err.failed_links().collect::<Vec<_>>(), vec![1]);
+    assert_eq!(err.link_is_true(0), Some(true));
+    assert_eq!(err.link_is_true(1), Some(false));
+    assert_eq!(err.link_is_true(2), None);
+
+    let ok = compare_variables!(0.0 < inside <= 1.0).unwrap();
+    assert_eq!(ok.failed_links().count(), 0);
+    assert_eq!(ok.to_string(), "`0.0 < inside (value: 0.5) <= 1.0` is true");
+
+    // Single links are not highlighted separately
+    let err = compare_variables!(too_large <= 1.0).unwrap_err();
+    assert_eq!(err.failed_links().collect::<Vec<_>>(), vec![0]);
+    assert_eq!(err.to_string(), "`too_large (value: 2.0) <= 1.0` is false");
+}
+
+#[test]
+fn test_incomparable() {
+    let x = f64::NAN;
+    let err = compare_variables!(x > 0.0).unwrap_err();
+    assert_eq!(err.outcome(), ComparisonOutcome::Incomparable);
+    assert!(err.is_incomparable());
+    assert!(!err.is_true());
+    assert_eq!(
+        err.to_string(),
+        "`x (value: NaN) > 0.0` is undecidable: values are not comparable"
+    );
+
+    // NaN contamination is also detected for (in)equality
+    let err = compare_variables!(x != 0.0).unwrap_err();
+    assert!(err.is_incomparable());
+
+    // An incomparable link dominates a false link
+    let err = compare_variables!(2.0 < 1.0 < x).unwrap_err();
+    assert_eq!(err.link_outcome(0), Some(ComparisonOutcome::False));
+    assert_eq!(err.link_outcome(1), Some(ComparisonOutcome::Incomparable));
+    assert_eq!(err.outcome(), ComparisonOutcome::Incomparable);
+    assert_eq!(
+        err.to_string(),
+        "`2.0 < 1.0 < x (value: NaN)` is undecidable: values are not comparable \
+        (failed: `2.0 < 1.0`, `1.0 < x (value: NaN)`)"
+    );
+
+    // Genuine range violations are still false
+    let x = 2.0;
+    let err = compare_variables!(0.0 < x < 1.0).unwrap_err();
+    assert_eq!(err.outcome(), ComparisonOutcome::False);
+    assert!(!err.is_incomparable());
+}
+
+#[test]
+fn test_comparison_error_trait() {
+    let err: Box<dyn ComparisonErrorTrait> =
+        Box::new(compare_variables!(0 < 1 <= 3 < 2).unwrap_err());
+    assert_eq!(err.len(), 4);
+    assert!(matches!(
+        err.operator(1),
+        Some(ComparisonOperator::LesserOrEqual)
+    ));
+    assert!(err.operator(3).is_none());
+
+    struct Val<'a>(&'a dyn ComparisonErrorTrait, usize);
+    impl std::fmt::Display for Val<'_> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            self.0.fmt_val(self.1, f)
+        }
+    }
+    assert_eq!(Val(err.as_ref(), 2).to_string(), "3");
+    assert!(std::fmt::write(&mut String::new(), format_args!("{}", Val(err.as_ref(), 4))).is_err());
+}
+
+#[test]
+fn test_comparison_error_trait_inspection() {
+    let (a, b) = (1.0, 0.5);
+    let err: Box<dyn ComparisonErrorTrait> =
+        Box::new(compare_variables!(owned a <= b as limit < 2.0).unwrap_err());
+    assert!(!err.is_true());
+    assert_eq!(err.outcome(), ComparisonOutcome::False);
+    assert_eq!(err.variable_name(0), Some("a"));
+    assert_eq!(err.variable_name(1), Some("limit"));
+    assert_eq!(err.variable_name(2), None);
+    assert_eq!(err.variable_name(3), None);
+    assert_eq!(err.value_debug(1).as_deref(), Some("0.5"));
+    assert_eq!(err.value_debug(2).as_deref(), Some("2.0"));
+    assert_eq!(err.value_debug(3), None);
+
+    let ok: Box<dyn ComparisonErrorTrait> = Box::new(compare_variables!(owned a < 2.0).unwrap());
+    assert!(ok.is_true());
+}
+
+#[test]
+fn test_comparison_error_trait_downcast() {
+    let x = 3;
+    let mut err: Box<dyn ComparisonErrorTrait> =
+        Box::new(compare_variables!(owned x > 10).unwrap_err());
+    assert!(err.is::<Comparison<i32>>());
+    assert!(!err.is::<Comparison<i64>>());
+    assert!(err.downcast_ref::<Comparison<f64>>().is_none());
+    assert_eq!(
+        err.downcast_ref::<Comparison<i32>>()
+            .unwrap()
+            .first_val()
+            .value,
+        3
+    );
+    assert!(err.downcast_mut::<Comparison<i32>>().is_some());
+
+    let err = err.downcast::<ComparisonReport>().unwrap_err();
+    let cmp = err.downcast::<Comparison<i32>>().unwrap();
+    assert_eq!(cmp.to_string(), "`x (value: 3) > 10` is false");
+}